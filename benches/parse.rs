@@ -0,0 +1,62 @@
+//! Simple throughput benchmarks for the header parsers
+//!
+//! Run with `cargo bench`; the benchmarks use the standard library only and print the throughput for each parser.
+
+use http_tiny::{Header, HeaderRef, ReadOptions};
+use std::{
+    hint,
+    io::BufReader,
+    time::{Duration, Instant},
+};
+
+/// The minimum runtime per benchmark
+const RUNTIME: Duration = Duration::from_secs(2);
+
+/// Creates a header with `count` fields of `len` bytes each
+fn header(count: usize, len: usize) -> Vec<u8> {
+    let mut header = b"GET /index.html?query=value HTTP/1.1\r\nHost: www.example.org\r\n".to_vec();
+    for index in 0..count {
+        let field = format!("X-Field-{index}: {}\r\n", "v".repeat(len));
+        header.extend_from_slice(field.as_bytes());
+    }
+    header.extend_from_slice(b"\r\n");
+    header
+}
+
+/// Runs `f` repeatedly over `header` and prints the throughput
+fn bench<F>(name: &str, header: &[u8], mut f: F)
+where
+    F: FnMut(&[u8]),
+{
+    let (start, mut iterations) = (Instant::now(), 0u64);
+    while start.elapsed() < RUNTIME {
+        f(hint::black_box(header));
+        iterations += 1;
+    }
+
+    // Compute the throughput
+    let elapsed = start.elapsed().as_secs_f64();
+    let throughput = (header.len() as f64 * iterations as f64) / elapsed / (1024.0 * 1024.0);
+    let per_header = elapsed / iterations as f64 * 1_000_000.0;
+    println!("{name:<32} {:>8} bytes: {throughput:>10.2} MiB/s ({per_header:>10.3} µs/header)", header.len());
+}
+
+fn main() {
+    let options = ReadOptions::unlimited();
+    for (count, len) in [(8, 32), (64, 256), (256, 1024)] {
+        let header = header(count, len);
+        bench("Header::read_with (BufReader)", &header, |mut header| {
+            let mut source = BufReader::new(&mut header);
+            hint::black_box(Header::read_with(&mut source, &options).expect("failed to read header"));
+        });
+        bench("Header::read_with (slice)", &header, |mut header| {
+            hint::black_box(Header::read_with(&mut header, &options).expect("failed to read header"));
+        });
+        bench("Header::parse_with", &header, |header| {
+            hint::black_box(Header::parse_with(header, &options).expect("failed to parse header"));
+        });
+        bench("HeaderRef::parse_with", &header, |header| {
+            hint::black_box(HeaderRef::parse_with(header, &options).expect("failed to parse header"));
+        });
+    }
+}
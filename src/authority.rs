@@ -0,0 +1,174 @@
+//! URI authorities for the `Host` field and authority-form targets
+
+use crate::error::Error;
+use std::{
+    net::{Ipv4Addr, Ipv6Addr},
+    str,
+};
+
+/// The host component of an authority (see RFC 3986, section 3.2.2)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Host<'a> {
+    /// A registered name like `example.com`
+    ///
+    /// ## Note
+    /// The name is stored as sent; see [`Authority::normalized`] for a case-insensitive representation.
+    RegName(&'a [u8]),
+    /// An IPv4 address like `192.0.2.1`
+    Ipv4(Ipv4Addr),
+    /// A bracketed IPv6 address like `[2001:db8::1]` or `[fe80::1%25eth0]` (see RFC 6874)
+    Ipv6 {
+        /// The address
+        address: Ipv6Addr,
+        /// The zone ID without the `%25` prefix if any
+        zone: Option<&'a [u8]>,
+    },
+}
+
+/// An URI authority without userinfo, like `example.com:8080` (see RFC 9110, section 4.2.3 and 7.2)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Authority<'a> {
+    /// The host
+    host: Host<'a>,
+    /// The port if any
+    port: Option<u16>,
+}
+impl<'a> Authority<'a> {
+    /// Parses an authority from `bytes` (e.g. the value of a `Host` field)
+    ///
+    /// ## Note
+    /// Authorities with userinfo (i.e. `user@host`) and empty hosts are rejected, as are ports above `65535`; an empty
+    /// port (i.e. `host:`) is treated as absent. The offset of the returned [`Error::InvalidFieldValue`] is relative to
+    /// `bytes`.
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, Error> {
+        // Reject userinfo which is deprecated for HTTP URIs (see RFC 9110, section 4.2.4)
+        if let Some(offset) = bytes.iter().position(|byte| *byte == b'@') {
+            return Err(Error::InvalidFieldValue { offset });
+        }
+
+        // Split host and port
+        let (host, port_offset) = match bytes {
+            [b'[', ..] => {
+                let end = bytes.iter().position(|byte| *byte == b']').ok_or(Error::InvalidFieldValue { offset: 0 })?;
+                (Self::parse_ipv6(&bytes[..=end])?, end + 1)
+            }
+            _ => {
+                let end = bytes.iter().position(|byte| *byte == b':').unwrap_or(bytes.len());
+                (Self::parse_reg_name(&bytes[..end])?, end)
+            }
+        };
+        let port = match &bytes[port_offset..] {
+            [] | [b':'] => None,
+            [b':', port @ ..] => Some(
+                Self::parse_port(port)
+                    .map_err(|offset| Error::InvalidFieldValue { offset: port_offset + 1 + offset })?,
+            ),
+            _ => return Err(Error::InvalidFieldValue { offset: port_offset }),
+        };
+        Ok(Self { host, port })
+    }
+    /// Parses a bracketed IPv6 literal with optional zone ID
+    fn parse_ipv6(bytes: &'a [u8]) -> Result<Host<'a>, Error> {
+        // Split and validate the zone ID (see RFC 6874, section 2)
+        let literal = &bytes[1..bytes.len() - 1];
+        let (address, zone) = match literal.iter().position(|byte| *byte == b'%') {
+            Some(separator) => {
+                let zone = match literal[separator..].strip_prefix(b"%25") {
+                    Some(zone) if !zone.is_empty() => zone,
+                    _ => return Err(Error::InvalidFieldValue { offset: 1 + separator }),
+                };
+                validate_pct_encoded(zone, is_unreserved)
+                    .map_err(|offset| Error::InvalidFieldValue { offset: 1 + separator + 3 + offset })?;
+                (&literal[..separator], Some(zone))
+            }
+            None => (literal, None),
+        };
+
+        // Parse the address
+        let address = str::from_utf8(address).ok().and_then(|address| address.parse().ok());
+        let address = address.ok_or(Error::InvalidFieldValue { offset: 1 })?;
+        Ok(Host::Ipv6 { address, zone })
+    }
+    /// Parses an IPv4 address or registered name
+    fn parse_reg_name(bytes: &'a [u8]) -> Result<Host<'a>, Error> {
+        // Validate the name
+        if bytes.is_empty() {
+            return Err(Error::InvalidFieldValue { offset: 0 });
+        }
+        validate_pct_encoded(bytes, |byte| is_unreserved(byte) || is_sub_delim(byte))
+            .map_err(|offset| Error::InvalidFieldValue { offset })?;
+
+        // Prefer the IPv4 interpretation (see RFC 3986, section 3.2.2)
+        match str::from_utf8(bytes).ok().and_then(|host| host.parse().ok()) {
+            Some(address) => Ok(Host::Ipv4(address)),
+            None => Ok(Host::RegName(bytes)),
+        }
+    }
+    /// Parses a port number
+    fn parse_port(bytes: &[u8]) -> Result<u16, usize> {
+        if let Some(offset) = bytes.iter().position(|byte| !byte.is_ascii_digit()) {
+            return Err(offset);
+        }
+
+        // Parse the number and check the range
+        let port = str::from_utf8(bytes).ok().and_then(|port| port.parse().ok());
+        port.ok_or(0)
+    }
+
+    /// The host
+    pub fn host(&self) -> Host<'a> {
+        self.host
+    }
+    /// The port if any
+    pub fn port(&self) -> Option<u16> {
+        self.port
+    }
+    /// The port or `default` if the authority has no port
+    pub fn port_or(&self, default: u16) -> u16 {
+        self.port.unwrap_or(default)
+    }
+
+    /// A normalized representation of the authority suitable for comparisons (e.g. for virtual-host routing)
+    ///
+    /// ## Note
+    /// Registered names are lowercased, IP addresses are serialized in their canonical form, and the port is omitted
+    /// if it is absent or equal to `default_port` (e.g. `80` for `http` or `443` for `https`).
+    pub fn normalized(&self, default_port: u16) -> Vec<u8> {
+        // Serialize the host
+        let mut normalized = match self.host {
+            Host::RegName(name) => name.to_ascii_lowercase(),
+            Host::Ipv4(address) => address.to_string().into_bytes(),
+            Host::Ipv6 { address, zone: None } => format!("[{address}]").into_bytes(),
+            Host::Ipv6 { address, zone: Some(zone) } => [format!("[{address}%25").as_bytes(), zone, b"]"].concat(),
+        };
+
+        // Append the port if it is not the default port
+        if let Some(port) = self.port.filter(|port| *port != default_port) {
+            normalized.extend_from_slice(format!(":{port}").as_bytes());
+        }
+        normalized
+    }
+}
+
+/// Validates that `bytes` consists of allowed or percent-encoded characters (see RFC 3986, section 2.1)
+fn validate_pct_encoded(bytes: &[u8], is_allowed: fn(u8) -> bool) -> Result<(), usize> {
+    let mut offset = 0;
+    while let Some(byte) = bytes.get(offset) {
+        match byte {
+            b'%' if bytes.get(offset + 1..offset + 3).is_some_and(|hex| hex.iter().all(u8::is_ascii_hexdigit)) => {
+                offset += 3
+            }
+            byte if is_allowed(*byte) => offset += 1,
+            _ => return Err(offset),
+        }
+    }
+    Ok(())
+}
+/// Tests whether `byte` is an URI `unreserved` character (see RFC 3986, section 2.3)
+const fn is_unreserved(byte: u8) -> bool {
+    matches!(byte, b'-' | b'.' | b'_' | b'~' | b'0'..=b'9' | b'a'..=b'z' | b'A'..=b'Z')
+}
+/// Tests whether `byte` is an URI `sub-delims` character (see RFC 3986, section 2.2)
+const fn is_sub_delim(byte: u8) -> bool {
+    matches!(byte, b'!' | b'$' | b'&' | b'\'' | b'(' | b')' | b'*' | b'+' | b',' | b';' | b'=')
+}
@@ -0,0 +1,415 @@
+//! Message body framing
+
+use crate::{
+    error::Error,
+    grammar,
+    header::{Header, HeaderFields},
+    iotraits::BufReadExt,
+    options::ReadOptions,
+};
+use std::{
+    io::{self, BufRead, ErrorKind, Read, Write},
+    str,
+};
+
+/// The maximum length of a chunk size line including chunk extensions and the line break
+const MAX_CHUNK_LINE: u64 = 4096;
+
+/// The framing of a message body (see RFC 9112, section 6.3)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Framing {
+    /// The message has no body
+    None,
+    /// The body has the given length in bytes (see [`ContentLengthReader`])
+    ContentLength(u64),
+    /// The body uses `chunked` transfer coding (see [`ChunkedReader`])
+    Chunked,
+    /// The body is delimited by the end of the connection
+    UntilClose,
+}
+
+/// A message body reader for any [`Framing`]
+#[derive(Debug)]
+pub enum Body<R> {
+    /// The message has no body
+    Empty(R),
+    /// The body has a fixed length
+    ContentLength(ContentLengthReader<R>),
+    /// The body uses `chunked` transfer coding
+    Chunked(ChunkedReader<R>),
+    /// The body is delimited by the end of the connection
+    UntilClose(R),
+}
+impl<R> Body<R> {
+    /// Creates a new reader for the body with the given `framing` from `source` using the default [`ReadOptions`]
+    pub const fn new(source: R, framing: Framing) -> Self {
+        Self::with_options(source, framing, ReadOptions::new())
+    }
+    /// Creates a new reader for the body with the given `framing` from `source` using the given `options` for the
+    /// trailer section of a chunked body
+    pub const fn with_options(source: R, framing: Framing, options: ReadOptions) -> Self {
+        match framing {
+            Framing::None => Self::Empty(source),
+            Framing::ContentLength(length) => Self::ContentLength(ContentLengthReader::new(source, length)),
+            Framing::Chunked => Self::Chunked(ChunkedReader::with_options(source, options)),
+            Framing::UntilClose => Self::UntilClose(source),
+        }
+    }
+
+    /// Returns the underlying source
+    ///
+    /// ## Note
+    /// If the body has not been read completely, the source is positioned within the body.
+    pub fn into_inner(self) -> R {
+        match self {
+            Self::Empty(source) | Self::UntilClose(source) => source,
+            Self::ContentLength(reader) => reader.into_inner(),
+            Self::Chunked(reader) => reader.into_inner(),
+        }
+    }
+}
+impl<R> Read for Body<R>
+where
+    R: BufRead,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Self::Empty(_) => Ok(0),
+            Self::ContentLength(reader) => reader.read(buf),
+            Self::Chunked(reader) => reader.read(buf),
+            Self::UntilClose(source) => source.read(buf),
+        }
+    }
+}
+
+/// A reader that yields exactly `Content-Length` bytes of a message body
+#[derive(Debug)]
+pub struct ContentLengthReader<R> {
+    /// The underlying source
+    source: R,
+    /// The amount of remaining body bytes
+    remaining: u64,
+}
+impl<R> ContentLengthReader<R> {
+    /// Creates a new reader that yields the next `length` bytes from `source`
+    pub const fn new(source: R, length: u64) -> Self {
+        Self { source, remaining: length }
+    }
+    /// Creates a new reader that yields the body declared by the `Content-Length` field of `header` from `source`
+    ///
+    /// ## Note
+    /// A missing `Content-Length` field is treated as empty body, which is only correct for requests without
+    /// `Transfer-Encoding`; use [`Header::body_framing`] to determine the framing of arbitrary messages.
+    pub fn from_header(header: &Header, source: R) -> Result<Self, Error> {
+        let length = header.fields().content_length()?.unwrap_or(0);
+        Ok(Self::new(source, length))
+    }
+
+    /// The amount of body bytes that have not been read yet
+    pub const fn remaining(&self) -> u64 {
+        self.remaining
+    }
+    /// Returns the underlying source
+    ///
+    /// ## Note
+    /// If the body has not been read completely, the source is positioned within the body.
+    pub fn into_inner(self) -> R {
+        self.source
+    }
+}
+impl<R> Read for ContentLengthReader<R>
+where
+    R: Read,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        // Limit the read to the remaining body
+        let len = usize::try_from(self.remaining).unwrap_or(usize::MAX).min(buf.len());
+        if len == 0 {
+            return Ok(0);
+        }
+
+        // Read the next bytes and fail on premature EOF
+        let read = self.source.read(&mut buf[..len])?;
+        if read == 0 {
+            return Err(Error::Incomplete.into());
+        }
+        self.remaining -= read as u64;
+        Ok(read)
+    }
+}
+impl<R> BufRead for ContentLengthReader<R>
+where
+    R: BufRead,
+{
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        // Get the next chunk and fail on premature EOF
+        if self.remaining == 0 {
+            return Ok(&[]);
+        }
+        let chunk = self.source.fill_buf()?;
+        if chunk.is_empty() {
+            return Err(Error::Incomplete.into());
+        }
+
+        // Limit the chunk to the remaining body
+        let len = usize::try_from(self.remaining).unwrap_or(usize::MAX).min(chunk.len());
+        Ok(&chunk[..len])
+    }
+    fn consume(&mut self, amt: usize) {
+        self.source.consume(amt);
+        self.remaining -= amt as u64;
+    }
+}
+
+/// A writer that enforces the declared `Content-Length` of a message body
+#[derive(Debug)]
+pub struct ContentLengthWriter<W> {
+    /// The underlying sink
+    sink: W,
+    /// The amount of remaining body bytes
+    remaining: u64,
+}
+impl<W> ContentLengthWriter<W> {
+    /// Creates a new writer that accepts exactly `length` bytes for `sink`
+    pub const fn new(sink: W, length: u64) -> Self {
+        Self { sink, remaining: length }
+    }
+    /// Creates a new writer that accepts exactly the body length declared by the `Content-Length` field of `header`
+    ///
+    /// ## Note
+    /// A missing `Content-Length` field is treated as empty body.
+    pub fn from_header(header: &Header, sink: W) -> Result<Self, Error> {
+        let length = header.fields().content_length()?.unwrap_or(0);
+        Ok(Self::new(sink, length))
+    }
+
+    /// The amount of body bytes that have not been written yet
+    pub const fn remaining(&self) -> u64 {
+        self.remaining
+    }
+}
+impl<W> ContentLengthWriter<W>
+where
+    W: Write,
+{
+    /// Ensures that the complete body has been written and returns the underlying sink
+    pub fn finish(mut self) -> Result<W, io::Error> {
+        if self.remaining > 0 {
+            return Err(io::Error::new(ErrorKind::InvalidInput, "body is shorter than the declared content length"));
+        }
+        self.sink.flush()?;
+        Ok(self.sink)
+    }
+}
+impl<W> Write for ContentLengthWriter<W>
+where
+    W: Write,
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        // Reject any data that exceeds the declared length
+        if buf.len() as u64 > self.remaining {
+            return Err(io::Error::new(ErrorKind::InvalidInput, "body exceeds the declared content length"));
+        }
+
+        // Write the data
+        let written = self.sink.write(buf)?;
+        self.remaining -= written as u64;
+        Ok(written)
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        self.sink.flush()
+    }
+}
+
+/// The decoding state of a chunked body
+#[derive(Debug)]
+enum ChunkedState {
+    /// The next chunk size line is expected
+    Size,
+    /// The data of the current chunk is expected
+    Data {
+        /// The amount of remaining chunk bytes
+        remaining: u64,
+    },
+    /// The line break after the data of the current chunk is expected
+    DataEnd,
+    /// The last chunk and the trailer section have been read
+    Done {
+        /// The trailer section
+        trailers: HeaderFields,
+    },
+}
+
+/// A reader that decodes a message body with `chunked` transfer coding (see RFC 9112, section 7.1)
+///
+/// ## Note
+/// Chunk extensions are ignored. The trailer section is available via [`ChunkedReader::trailers`] once the body has been
+/// read completely; it is subject to the field limits of the [`ReadOptions`], and exceeding a limit fails with
+/// [`Error::LimitExceeded`].
+#[derive(Debug)]
+pub struct ChunkedReader<R> {
+    /// The underlying source
+    source: R,
+    /// The options for reading the trailer section
+    options: ReadOptions,
+    /// The decoding state
+    state: ChunkedState,
+}
+impl<R> ChunkedReader<R> {
+    /// Creates a new reader that decodes the chunked body from `source` using the default [`ReadOptions`]
+    pub const fn new(source: R) -> Self {
+        Self::with_options(source, ReadOptions::new())
+    }
+    /// Creates a new reader that decodes the chunked body from `source` using the given `options` for the trailer
+    /// section
+    pub const fn with_options(source: R, options: ReadOptions) -> Self {
+        Self { source, options, state: ChunkedState::Size }
+    }
+
+    /// The trailer section if the body has been read completely
+    pub fn trailers(&self) -> Option<&HeaderFields> {
+        match &self.state {
+            ChunkedState::Done { trailers } => Some(trailers),
+            _ => None,
+        }
+    }
+    /// Returns the underlying source
+    ///
+    /// ## Note
+    /// If the body has not been read completely, the source is positioned within the body.
+    pub fn into_inner(self) -> R {
+        self.source
+    }
+}
+impl<R> ChunkedReader<R>
+where
+    R: BufRead,
+{
+    /// Reads a line and returns it without the line break
+    fn read_line(&mut self) -> Result<Vec<u8>, Error> {
+        // Read the line
+        let mut line = (&mut self.source).take(MAX_CHUNK_LINE).read_word("\n", [])?;
+        match line.pop() {
+            Some(b'\n') => (),
+            _ if line.len() as u64 + 1 >= MAX_CHUNK_LINE => return Err(Error::InvalidChunk),
+            _ => return Err(Error::Incomplete),
+        }
+        if line.last() == Some(&b'\r') {
+            line.pop();
+        }
+        Ok(line)
+    }
+    /// Reads the next chunk size line and returns the chunk size
+    fn read_size(&mut self) -> Result<u64, Error> {
+        // Parse the hexadecimal chunk size (note: this also detects overflows)
+        let line = self.read_line()?;
+        let digits = line.iter().take_while(|b| b.is_ascii_hexdigit()).count();
+        let size = str::from_utf8(&line[..digits]).ok().and_then(|digits| u64::from_str_radix(digits, 16).ok());
+
+        // Validate the chunk extensions
+        match (size, &line[digits..]) {
+            (Some(size), extensions) if Self::is_valid_extensions(extensions) => Ok(size),
+            _ => Err(Error::InvalidChunk),
+        }
+    }
+    /// Tests whether `extensions` is a plausible sequence of chunk extensions (see RFC 9112, section 7.1.1)
+    fn is_valid_extensions(extensions: &[u8]) -> bool {
+        let leading_whitespace = extensions.iter().take_while(|b| grammar::is_ows(**b)).count();
+        match &extensions[leading_whitespace..] {
+            [] => true,
+            [b';', ..] => extensions.iter().all(|b| grammar::is_field_vchar(*b) || grammar::is_ows(*b)),
+            _ => false,
+        }
+    }
+}
+impl<R> Read for ChunkedReader<R>
+where
+    R: BufRead,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            match self.state {
+                ChunkedState::Size => match self.read_size()? {
+                    0 => {
+                        let trailers = HeaderFields::read_with(&mut self.source, &self.options)?;
+                        self.state = ChunkedState::Done { trailers };
+                    }
+                    remaining => self.state = ChunkedState::Data { remaining },
+                },
+                ChunkedState::Data { remaining } => {
+                    // Limit the read to the remaining chunk
+                    let len = usize::try_from(remaining).unwrap_or(usize::MAX).min(buf.len());
+                    if len == 0 {
+                        return Ok(0);
+                    }
+
+                    // Read the next bytes and fail on premature EOF
+                    let read = self.source.read(&mut buf[..len])?;
+                    self.state = match remaining - read as u64 {
+                        _ if read == 0 => return Err(Error::Incomplete.into()),
+                        0 => ChunkedState::DataEnd,
+                        remaining => ChunkedState::Data { remaining },
+                    };
+                    return Ok(read);
+                }
+                ChunkedState::DataEnd => match self.read_line()?.as_slice() {
+                    [] => self.state = ChunkedState::Size,
+                    _ => return Err(Error::InvalidChunk.into()),
+                },
+                ChunkedState::Done { .. } => return Ok(0),
+            }
+        }
+    }
+}
+
+/// A writer that encodes a message body with `chunked` transfer coding (see RFC 9112, section 7.1)
+///
+/// ## Note
+/// Every write is emitted as a separate chunk; wrap the writer into a [`std::io::BufWriter`] to avoid tiny chunks.
+#[derive(Debug)]
+pub struct ChunkedWriter<W> {
+    /// The underlying sink
+    sink: W,
+}
+impl<W> ChunkedWriter<W> {
+    /// Creates a new writer that encodes the chunked body into `sink`
+    pub const fn new(sink: W) -> Self {
+        Self { sink }
+    }
+}
+impl<W> ChunkedWriter<W>
+where
+    W: Write,
+{
+    /// Writes the last chunk without trailers and returns the underlying sink
+    pub fn finish(self) -> Result<W, io::Error> {
+        self.finish_with_trailers(&HeaderFields::new())
+    }
+    /// Writes the last chunk followed by `trailers` and returns the underlying sink
+    pub fn finish_with_trailers(mut self, trailers: &HeaderFields) -> Result<W, io::Error> {
+        self.sink.write_all(b"0\r\n")?;
+        trailers.write_all(&mut self.sink)?;
+        self.sink.flush()?;
+        Ok(self.sink)
+    }
+}
+impl<W> Write for ChunkedWriter<W>
+where
+    W: Write,
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        // Skip empty writes, since an empty chunk would terminate the body
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        // Write the chunk
+        write!(self.sink, "{:X}\r\n", buf.len())?;
+        self.sink.write_all(buf)?;
+        self.sink.write_all(b"\r\n")?;
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        self.sink.flush()
+    }
+}
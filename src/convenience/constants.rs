@@ -15,8 +15,61 @@ pub const STATUS_500_INTERNALSERVERERROR: &str = "500";
 pub const HEADER_CONTENTTYPE: &str = "content-type";
 /// The header key for the "Content-Length" header field
 pub const HEADER_CONTENTLENGTH: &str = "content-length";
+/// The header key for the "Date" header field
+pub const HEADER_DATE: &str = "date";
 
 /// Content type constant for "text/plain"
 pub const CONTENTTYPE_TEXTPLAIN: &str = "text/plain";
+/// Content type constant for "text/plain; charset=utf-8"
+pub const CONTENTTYPE_TEXTPLAIN_UTF8: &str = "text/plain; charset=utf-8";
+/// Content type constant for "text/html"
+pub const CONTENTTYPE_TEXTHTML: &str = "text/html";
+/// Content type constant for "text/html; charset=utf-8"
+pub const CONTENTTYPE_TEXTHTML_UTF8: &str = "text/html; charset=utf-8";
+/// Content type constant for "text/css"
+pub const CONTENTTYPE_TEXTCSS: &str = "text/css";
+/// Content type constant for "text/csv"
+pub const CONTENTTYPE_TEXTCSV: &str = "text/csv";
+/// Content type constant for "text/javascript"
+pub const CONTENTTYPE_TEXTJAVASCRIPT: &str = "text/javascript";
+/// Content type constant for "text/event-stream"
+pub const CONTENTTYPE_TEXTEVENTSTREAM: &str = "text/event-stream";
 /// Content type constant for "application/octet-stream"
 pub const CONTENTTYPE_APPLICATIONOCTETSTREAM: &str = "application/octet-stream";
+/// Content type constant for "application/json"
+pub const CONTENTTYPE_APPLICATIONJSON: &str = "application/json";
+/// Content type constant for "application/cbor"
+pub const CONTENTTYPE_APPLICATIONCBOR: &str = "application/cbor";
+/// Content type constant for "application/xml"
+pub const CONTENTTYPE_APPLICATIONXML: &str = "application/xml";
+/// Content type constant for "application/pdf"
+pub const CONTENTTYPE_APPLICATIONPDF: &str = "application/pdf";
+/// Content type constant for "application/zip"
+pub const CONTENTTYPE_APPLICATIONZIP: &str = "application/zip";
+/// Content type constant for "application/wasm"
+pub const CONTENTTYPE_APPLICATIONWASM: &str = "application/wasm";
+/// Content type constant for "application/x-www-form-urlencoded"
+pub const CONTENTTYPE_APPLICATIONFORMURLENCODED: &str = "application/x-www-form-urlencoded";
+/// Content type constant for "multipart/form-data"
+///
+/// ## Note
+/// A `boundary` parameter is required for multipart types (see RFC 2046, section 5.1.1).
+pub const CONTENTTYPE_MULTIPARTFORMDATA: &str = "multipart/form-data";
+/// Content type constant for "image/png"
+pub const CONTENTTYPE_IMAGEPNG: &str = "image/png";
+/// Content type constant for "image/jpeg"
+pub const CONTENTTYPE_IMAGEJPEG: &str = "image/jpeg";
+/// Content type constant for "image/gif"
+pub const CONTENTTYPE_IMAGEGIF: &str = "image/gif";
+/// Content type constant for "image/webp"
+pub const CONTENTTYPE_IMAGEWEBP: &str = "image/webp";
+/// Content type constant for "image/svg+xml"
+pub const CONTENTTYPE_IMAGESVGXML: &str = "image/svg+xml";
+/// Content type constant for "image/x-icon"
+pub const CONTENTTYPE_IMAGEXICON: &str = "image/x-icon";
+/// Content type constant for "audio/mpeg"
+pub const CONTENTTYPE_AUDIOMPEG: &str = "audio/mpeg";
+/// Content type constant for "video/mp4"
+pub const CONTENTTYPE_VIDEOMP4: &str = "video/mp4";
+/// Content type constant for "font/woff2"
+pub const CONTENTTYPE_FONTWOFF2: &str = "font/woff2";
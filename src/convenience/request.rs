@@ -1,9 +1,10 @@
 //! Request builder extensions
 
 use crate::{
-    bytetraits::IntoBytes, convenience::constants::HEADER_CONTENTLENGTH, Header, HeaderFields, HeaderStartLine,
+    bytetraits::IntoBytes, convenience::constants::HEADER_CONTENTLENGTH, Error, Header, HeaderFields, HeaderStartLine,
+    Method, RequestTarget,
 };
-use std::io::{self, BufReader, Error, Stdin};
+use std::io::{self, BufReader, Stdin};
 
 /// A request builder extension trait
 pub trait RequestBuilder
@@ -12,12 +13,27 @@ where
 {
     /// Creates a new request
     fn new_request<M, T>(method: M, target: T) -> Self
+    where
+        M: IntoBytes,
+        T: IntoBytes;
+    /// Creates a new request or fails if `method` is not a valid token or `target` is not a valid request target
+    fn try_new_request<M, T>(method: M, target: T) -> Result<Self, Error>
     where
         M: IntoBytes,
         T: IntoBytes;
 
     /// Sets an arbitrary header key-value pair
+    ///
+    /// # Panics
+    /// In debug builds, this function panics if `key` is not a valid token or `value` is not a valid field value; use
+    /// `try_set_header` to validate untrusted input.
     fn set_header<K, V>(self, key: K, value: V) -> Self
+    where
+        K: IntoBytes,
+        V: IntoBytes;
+    /// Sets an arbitrary header key-value pair or fails if `key` is not a valid token or `value` is not a valid field
+    /// value
+    fn try_set_header<K, V>(self, key: K, value: V) -> Result<Self, Error>
     where
         K: IntoBytes,
         V: IntoBytes;
@@ -41,6 +57,16 @@ impl RequestBuilder for Header {
         Header::new(start_line, fields)
     }
 
+    fn try_new_request<M, T>(method: M, target: T) -> Result<Self, Error>
+    where
+        M: IntoBytes,
+        T: IntoBytes,
+    {
+        let start_line = HeaderStartLine::try_new_request(method, target)?;
+        let fields = HeaderFields::new();
+        Ok(Header::new(start_line, fields))
+    }
+
     fn set_header<K, V>(mut self, key: K, value: V) -> Self
     where
         K: IntoBytes,
@@ -49,6 +75,15 @@ impl RequestBuilder for Header {
         self.fields_mut().set(key, value);
         self
     }
+
+    fn try_set_header<K, V>(mut self, key: K, value: V) -> Result<Self, Error>
+    where
+        K: IntoBytes,
+        V: IntoBytes,
+    {
+        self.fields_mut().try_set(key, value)?;
+        Ok(self)
+    }
 }
 
 /// Adds convenience methods to access a header as request header
@@ -59,10 +94,20 @@ where
     /// Reads `Self` from stdin (e.g. in CGI contexts)
     fn from_stdin() -> Result<(Self, BufReader<Stdin>), Error>;
 
-    /// The request method
+    /// The request method (or an empty slice if the header is not a request header)
     fn method(&self) -> &[u8];
-    /// The request target URL
+    /// The request method as [`Method`]
+    fn method_typed(&self) -> Result<Method, Error>;
+    /// The request target URL (or an empty slice if the header is not a request header)
     fn target(&self) -> &[u8];
+    /// The request target as [`RequestTarget`]
+    fn target_typed(&self) -> Result<RequestTarget<'_>, Error>;
+    /// The effective request URI reconstructed from the request target and the `Host` field (see
+    /// [`RequestTarget::effective_uri`])
+    ///
+    /// ## Note
+    /// `scheme` is the scheme of the connection (e.g. `https` if the request was received over TLS).
+    fn effective_uri(&self, scheme: &[u8]) -> Result<Option<Vec<u8>>, Error>;
     /// The request HTTP version
     fn version(&self) -> &[u8];
 }
@@ -74,14 +119,27 @@ impl RequestHeader for Header {
     }
 
     fn method(&self) -> &[u8] {
-        self.start_line().field0()
+        self.start_line().method().unwrap_or_default()
+    }
+
+    fn method_typed(&self) -> Result<Method, Error> {
+        Method::parse(self.method())
     }
 
     fn target(&self) -> &[u8] {
-        self.start_line().field1()
+        self.start_line().target().unwrap_or_default()
+    }
+
+    fn target_typed(&self) -> Result<RequestTarget<'_>, Error> {
+        self.start_line().target_typed()
+    }
+
+    fn effective_uri(&self, scheme: &[u8]) -> Result<Option<Vec<u8>>, Error> {
+        let host = self.fields().get("host");
+        Ok(self.target_typed()?.effective_uri(scheme, host))
     }
 
     fn version(&self) -> &[u8] {
-        self.start_line().field2()
+        self.start_line().version()
     }
 }
@@ -2,10 +2,13 @@
 
 use crate::{
     bytetraits::IntoBytes,
-    convenience::constants::{HEADER_CONTENTLENGTH, HEADER_CONTENTTYPE},
-    Header, HeaderFields, HeaderStartLine,
+    convenience::constants::{HEADER_CONTENTLENGTH, HEADER_CONTENTTYPE, HEADER_DATE},
+    Error, Header, HeaderFields, HeaderStartLine, HttpDate, StatusCode,
+};
+use std::{
+    io::{self, BufReader, Stdin},
+    time::SystemTime,
 };
-use std::io::{self, BufReader, Error, Stdin};
 
 /// A response builder extension trait
 pub trait ResponseBuilder
@@ -14,17 +17,33 @@ where
 {
     /// Creates a new response
     fn new_response<S, R>(status: S, reason: R) -> Self
+    where
+        S: IntoBytes,
+        R: IntoBytes;
+    /// Creates a new response or fails if `status` is not a three-digit status code or `reason` is not a valid reason
+    /// phrase
+    fn try_new_response<S, R>(status: S, reason: R) -> Result<Self, Error>
     where
         S: IntoBytes,
         R: IntoBytes;
 
     /// Sets an arbitrary header key-value pair
+    ///
+    /// # Panics
+    /// In debug builds, this function panics if `key` is not a valid token or `value` is not a valid field value; use
+    /// `try_set_header` to validate untrusted input.
     fn set_header<K, V>(self, key: K, value: V) -> Self
+    where
+        K: IntoBytes,
+        V: IntoBytes;
+    /// Sets an arbitrary header key-value pair or fails if `key` is not a valid token or `value` is not a valid field
+    /// value
+    fn try_set_header<K, V>(self, key: K, value: V) -> Result<Self, Error>
     where
         K: IntoBytes,
         V: IntoBytes;
 
-    /// Sets the status/reason tuple for `self`
+    /// Sets the "Content-Type" header for `self` (e.g. from a [`crate::MediaType`])
     fn set_content_type<T>(self, type_: T) -> Self
     where
         T: IntoBytes,
@@ -39,6 +58,11 @@ where
     {
         self.set_header(HEADER_CONTENTLENGTH, length)
     }
+
+    /// Sets the "Date" header for `self` as IMF-fixdate
+    fn set_date(self, date: SystemTime) -> Self {
+        self.set_header(HEADER_DATE, HttpDate::from(date))
+    }
 }
 impl ResponseBuilder for Header {
     fn new_response<S, R>(status: S, reason: R) -> Self
@@ -51,6 +75,16 @@ impl ResponseBuilder for Header {
         Header::new(start_line, fields)
     }
 
+    fn try_new_response<S, R>(status: S, reason: R) -> Result<Self, Error>
+    where
+        S: IntoBytes,
+        R: IntoBytes,
+    {
+        let start_line = HeaderStartLine::try_new_response(status, reason)?;
+        let fields = HeaderFields::new();
+        Ok(Header::new(start_line, fields))
+    }
+
     fn set_header<K, V>(mut self, key: K, value: V) -> Self
     where
         K: IntoBytes,
@@ -59,6 +93,15 @@ impl ResponseBuilder for Header {
         self.fields_mut().set(key, value);
         self
     }
+
+    fn try_set_header<K, V>(mut self, key: K, value: V) -> Result<Self, Error>
+    where
+        K: IntoBytes,
+        V: IntoBytes,
+    {
+        self.fields_mut().try_set(key, value)?;
+        Ok(self)
+    }
 }
 
 /// Adds convenience methods to access a header as response header
@@ -69,9 +112,11 @@ where
     /// Reads `Self` from stdin (e.g. in CGI contexts)
     fn from_stdin() -> Result<(Self, BufReader<Stdin>), Error>;
 
-    /// The response status code
+    /// The response status code (or an empty slice if the header is not a response header)
     fn status(&self) -> &[u8];
-    /// The response status reason
+    /// The response status code as [`StatusCode`]
+    fn status_typed(&self) -> Result<StatusCode, Error>;
+    /// The response status reason (or an empty slice if the header is not a response header)
     fn reason(&self) -> &[u8];
     /// The response HTTP version
     fn version(&self) -> &[u8];
@@ -84,14 +129,18 @@ impl ResponseHeader for Header {
     }
 
     fn status(&self) -> &[u8] {
-        self.start_line().field1()
+        self.start_line().status().unwrap_or_default()
+    }
+
+    fn status_typed(&self) -> Result<StatusCode, Error> {
+        StatusCode::parse(self.status())
     }
 
     fn reason(&self) -> &[u8] {
-        self.start_line().field2()
+        self.start_line().reason().unwrap_or_default()
     }
 
     fn version(&self) -> &[u8] {
-        self.start_line().field0()
+        self.start_line().version()
     }
 }
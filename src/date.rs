@@ -0,0 +1,226 @@
+//! HTTP dates
+
+use crate::{bytetraits::IntoBytes, error::Error};
+use std::{
+    borrow::Cow,
+    fmt::{self, Display, Formatter},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// The abbreviated day names starting with Monday
+const DAY_NAMES: [&[u8]; 7] = [b"Mon", b"Tue", b"Wed", b"Thu", b"Fri", b"Sat", b"Sun"];
+/// The full day names starting with Monday
+const DAY_NAMES_LONG: [&[u8]; 7] =
+    [b"Monday", b"Tuesday", b"Wednesday", b"Thursday", b"Friday", b"Saturday", b"Sunday"];
+/// The abbreviated month names
+const MONTH_NAMES: [&[u8]; 12] =
+    [b"Jan", b"Feb", b"Mar", b"Apr", b"May", b"Jun", b"Jul", b"Aug", b"Sep", b"Oct", b"Nov", b"Dec"];
+/// The latest representable date (i.e. `Fri, 31 Dec 9999 23:59:59 GMT`) as seconds since the UNIX epoch
+const MAX_SECS: u64 = 253_402_300_799;
+
+/// A HTTP date with second precision (see RFC 9110, section 5.6.7)
+///
+/// ## Note
+/// Dates are limited to the years `1970` to `9999`; conversions from [`SystemTime`] saturate at these bounds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HttpDate {
+    /// The seconds since the UNIX epoch
+    secs: u64,
+}
+impl HttpDate {
+    /// Parses a date in IMF-fixdate, RFC 850 or asctime format
+    ///
+    /// ## Note
+    /// The day name is validated but not checked against the date. Two-digit RFC 850 years that would lie more than 50
+    /// years in the future are interpreted as past years. The offset of the returned [`Error::InvalidFieldValue`] is
+    /// relative to `bytes`.
+    pub fn parse(bytes: &[u8]) -> Result<Self, Error> {
+        let mut cursor = Cursor { bytes, offset: 0 };
+        let (year, month, day, time) = match bytes.get(3) {
+            // `Sun, 06 Nov 1994 08:49:37 GMT`
+            Some(b',') => {
+                cursor.name(&DAY_NAMES)?;
+                cursor.literal(b", ")?;
+                let day = cursor.digits(2)?;
+                cursor.literal(b" ")?;
+                let month = cursor.name(&MONTH_NAMES)?;
+                cursor.literal(b" ")?;
+                let year = cursor.digits(4)?;
+                cursor.literal(b" ")?;
+                let time = cursor.time()?;
+                cursor.literal(b" GMT")?;
+                (year, month, day, time)
+            }
+            // `Sun Nov  6 08:49:37 1994`
+            Some(b' ') => {
+                cursor.name(&DAY_NAMES)?;
+                cursor.literal(b" ")?;
+                let month = cursor.name(&MONTH_NAMES)?;
+                cursor.literal(b" ")?;
+                let day = match cursor.literal(b" ") {
+                    Ok(()) => cursor.digits(1)?,
+                    Err(_) => cursor.digits(2)?,
+                };
+                cursor.literal(b" ")?;
+                let time = cursor.time()?;
+                cursor.literal(b" ")?;
+                let year = cursor.digits(4)?;
+                (year, month, day, time)
+            }
+            // `Sunday, 06-Nov-94 08:49:37 GMT`
+            _ => {
+                cursor.name(&DAY_NAMES_LONG)?;
+                cursor.literal(b", ")?;
+                let day = cursor.digits(2)?;
+                cursor.literal(b"-")?;
+                let month = cursor.name(&MONTH_NAMES)?;
+                cursor.literal(b"-")?;
+                let year = Self::expand_year(cursor.digits(2)?);
+                cursor.literal(b" ")?;
+                let time = cursor.time()?;
+                cursor.literal(b" GMT")?;
+                (year, month, day, time)
+            }
+        };
+
+        // Ensure that the input has been consumed completely
+        if cursor.offset != bytes.len() {
+            return Err(Error::InvalidFieldValue { offset: cursor.offset });
+        }
+
+        // Validate the date
+        let is_valid_day = (1..=days_in_month(year, month as u64 + 1)).contains(&day);
+        if !(1970..=9999).contains(&year) || !is_valid_day {
+            return Err(Error::InvalidFieldValue { offset: 0 });
+        }
+        let secs = days_from_civil(year, month as u64 + 1, day) * 86_400 + time;
+        Ok(Self { secs: secs.min(MAX_SECS) })
+    }
+    /// Expands a two-digit RFC 850 year relative to the current year (see RFC 9110, section 5.6.7)
+    fn expand_year(year: u64) -> u64 {
+        let (current_year, ..) = civil_from_days(Self::from(SystemTime::now()).secs / 86_400);
+        let year = current_year - current_year % 100 + year;
+        match year > current_year + 50 {
+            true => year - 100,
+            false => year,
+        }
+    }
+}
+impl From<SystemTime> for HttpDate {
+    fn from(time: SystemTime) -> Self {
+        let secs = time.duration_since(UNIX_EPOCH).map(|duration| duration.as_secs()).unwrap_or_default();
+        Self { secs: secs.min(MAX_SECS) }
+    }
+}
+impl From<HttpDate> for SystemTime {
+    fn from(date: HttpDate) -> Self {
+        UNIX_EPOCH + Duration::from_secs(date.secs)
+    }
+}
+impl Display for HttpDate {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        // Compute the date components; the UNIX epoch was a Thursday
+        let days = self.secs / 86_400;
+        let (year, month, day) = civil_from_days(days);
+        let day_name = DAY_NAMES[((days + 3) % 7) as usize];
+        let month_name = MONTH_NAMES[month as usize - 1];
+        let (hour, minute, second) = (self.secs % 86_400 / 3600, self.secs % 3600 / 60, self.secs % 60);
+
+        // Always emit the IMF-fixdate format
+        let (day_name, month_name) = (String::from_utf8_lossy(day_name), String::from_utf8_lossy(month_name));
+        write!(f, "{day_name}, {day:02} {month_name} {year:04} {hour:02}:{minute:02}:{second:02} GMT")
+    }
+}
+impl IntoBytes for HttpDate {
+    fn into_bytes(self) -> Cow<'static, [u8]> {
+        Cow::Owned(self.to_string().into_bytes())
+    }
+}
+
+/// A cursor over the bytes of a date
+struct Cursor<'a> {
+    /// The date
+    bytes: &'a [u8],
+    /// The current offset
+    offset: usize,
+}
+impl Cursor<'_> {
+    /// Consumes `literal`
+    fn literal(&mut self, literal: &[u8]) -> Result<(), Error> {
+        if !self.bytes[self.offset..].starts_with(literal) {
+            return Err(Error::InvalidFieldValue { offset: self.offset });
+        }
+        self.offset += literal.len();
+        Ok(())
+    }
+    /// Consumes one of `names` and returns its index
+    fn name(&mut self, names: &[&[u8]]) -> Result<usize, Error> {
+        let index = names.iter().position(|name| self.bytes[self.offset..].starts_with(name));
+        let index = index.ok_or(Error::InvalidFieldValue { offset: self.offset })?;
+        self.offset += names[index].len();
+        Ok(index)
+    }
+    /// Consumes exactly `count` digits and returns their value
+    fn digits(&mut self, count: usize) -> Result<u64, Error> {
+        let mut value = 0;
+        for _ in 0..count {
+            match self.bytes.get(self.offset) {
+                Some(digit) if digit.is_ascii_digit() => value = value * 10 + u64::from(digit - b'0'),
+                _ => return Err(Error::InvalidFieldValue { offset: self.offset }),
+            }
+            self.offset += 1;
+        }
+        Ok(value)
+    }
+    /// Consumes a `time-of-day` (i.e. `hh:mm:ss`) and returns the seconds since midnight
+    fn time(&mut self) -> Result<u64, Error> {
+        let offset = self.offset;
+        let hour = self.digits(2)?;
+        self.literal(b":")?;
+        let minute = self.digits(2)?;
+        self.literal(b":")?;
+        let second = self.digits(2)?;
+
+        // Allow leap seconds (see RFC 5322, section 3.3)
+        match hour < 24 && minute < 60 && second <= 60 {
+            true => Ok(hour * 3600 + minute * 60 + second),
+            false => Err(Error::InvalidFieldValue { offset }),
+        }
+    }
+}
+
+/// Tests whether `year` is a leap year
+const fn is_leap_year(year: u64) -> bool {
+    year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400))
+}
+/// The number of days in the 1-based `month` of `year`
+const fn days_in_month(year: u64, month: u64) -> u64 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+/// The days since the UNIX epoch for the given date on or after `1970-01-01`
+const fn days_from_civil(year: u64, month: u64, day: u64) -> u64 {
+    // Shift the year to start in March (see <https://howardhinnant.github.io/date_algorithms.html>)
+    let year = if month <= 2 { year - 1 } else { year };
+    let (era, year_of_era) = (year / 400, year % 400);
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+/// The date as `(year, month, day)` for the given days since the UNIX epoch
+const fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    // Shift the epoch to `0000-03-01` (see <https://howardhinnant.github.io/date_algorithms.html>)
+    let days = days + 719_468;
+    let (era, day_of_era) = (days / 146_097, days % 146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = era * 400 + year_of_era + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
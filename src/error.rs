@@ -0,0 +1,100 @@
+//! The crate's error type
+
+use crate::options::Limit;
+use std::{
+    error,
+    fmt::{self, Display, Formatter},
+    io::{self, ErrorKind},
+};
+
+/// A HTTP message related error
+///
+/// ## Note
+/// The `offset` of a variant is the byte offset of the offending line (or byte, if applicable) within the processed
+/// input.
+#[derive(Debug)]
+pub enum Error {
+    /// The input ended before the header or body was complete
+    Incomplete,
+    /// The start line is malformed
+    InvalidStartLine {
+        /// The byte offset of the invalid data
+        offset: usize,
+    },
+    /// A header field name is malformed or missing
+    InvalidFieldName {
+        /// The byte offset of the invalid data
+        offset: usize,
+    },
+    /// A header field value is malformed
+    InvalidFieldValue {
+        /// The byte offset of the invalid data
+        offset: usize,
+    },
+    /// The `Content-Length` field is malformed or has conflicting values
+    InvalidContentLength,
+    /// The `Transfer-Encoding` field is malformed, unsupported or conflicts with the `Content-Length` field
+    InvalidTransferEncoding,
+    /// A chunk of a chunked body is malformed
+    InvalidChunk,
+    /// A parser limit has been exceeded
+    LimitExceeded {
+        /// The exceeded limit
+        limit: Limit,
+        /// The byte offset of the line that exceeded the limit
+        offset: usize,
+    },
+    /// An I/O error occurred
+    Io(io::Error),
+}
+impl Error {
+    /// The corresponding I/O error kind
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Incomplete => ErrorKind::UnexpectedEof,
+            Self::Io(error) => error.kind(),
+            _ => ErrorKind::InvalidData,
+        }
+    }
+}
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Incomplete => write!(f, "Incomplete HTTP message"),
+            Self::InvalidStartLine { offset } => write!(f, "Invalid HTTP start line (at offset {offset})"),
+            Self::InvalidFieldName { offset } => write!(f, "Invalid HTTP header field name (at offset {offset})"),
+            Self::InvalidFieldValue { offset } => write!(f, "Invalid HTTP header field value (at offset {offset})"),
+            Self::InvalidContentLength => write!(f, "Invalid HTTP content length"),
+            Self::InvalidTransferEncoding => write!(f, "Invalid or conflicting HTTP transfer encoding"),
+            Self::InvalidChunk => write!(f, "Invalid HTTP body chunk"),
+            Self::LimitExceeded { limit, offset } => write!(f, "{limit} (at offset {offset})"),
+            Self::Io(error) => write!(f, "I/O error: {error}"),
+        }
+    }
+}
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        // Unwrap the error if it is a wrapped `Self`
+        if error.get_ref().is_some_and(|inner| inner.is::<Self>()) {
+            let inner = error.into_inner().expect("missing inner error");
+            return *inner.downcast().expect("unexpected inner error type");
+        }
+        Self::Io(error)
+    }
+}
+impl From<Error> for io::Error {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::Io(error) => error,
+            error => io::Error::new(error.kind(), error),
+        }
+    }
+}
@@ -0,0 +1,147 @@
+//! Grammar rules from RFC 9110 and RFC 9112
+//!
+//! All validators return `Err(offset)` with the offset of the first invalid byte on failure.
+
+/// Tests whether `byte` is a `tchar` (see RFC 9110, section 5.6.2)
+pub const fn is_tchar(byte: u8) -> bool {
+    matches!(byte,
+        b'!' | b'#' | b'$' | b'%' | b'&' | b'\'' | b'*' | b'+' | b'-' | b'.' | b'^' | b'_' | b'`' | b'|' | b'~'
+        | b'0'..=b'9' | b'a'..=b'z' | b'A'..=b'Z')
+}
+/// Tests whether `byte` is a `field-vchar` (i.e. `VCHAR` or `obs-text`, see RFC 9110, section 5.5)
+pub const fn is_field_vchar(byte: u8) -> bool {
+    matches!(byte, 0x21..=0x7E | 0x80..=0xFF)
+}
+/// Tests whether `byte` is optional whitespace (i.e. `SP` or `HTAB`)
+pub const fn is_ows(byte: u8) -> bool {
+    matches!(byte, b' ' | b'\t')
+}
+
+/// Validates that `bytes` is a non-empty `token` (see RFC 9110, section 5.6.2)
+pub fn validate_token(bytes: &[u8]) -> Result<(), usize> {
+    match bytes.iter().position(|byte| !is_tchar(*byte)) {
+        _ if bytes.is_empty() => Err(0),
+        Some(offset) => Err(offset),
+        None => Ok(()),
+    }
+}
+/// Validates that `bytes` is a `field-value` without leading or trailing whitespace (see RFC 9110, section 5.5)
+pub fn validate_field_value(bytes: &[u8]) -> Result<(), usize> {
+    // Validate the characters
+    if let Some(offset) = bytes.iter().position(|byte| !is_field_vchar(*byte) && !is_ows(*byte)) {
+        return Err(offset);
+    }
+
+    // Validate that there is no leading or trailing whitespace
+    match (bytes.first(), bytes.last()) {
+        (Some(first), _) if is_ows(*first) => Err(0),
+        (_, Some(last)) if is_ows(*last) => Err(bytes.len() - 1),
+        _ => Ok(()),
+    }
+}
+/// Validates that `bytes` is a non-empty sequence of visible ASCII characters, which is a necessary condition for a
+/// `request-target` (see RFC 9112, section 3.2)
+pub fn validate_request_target(bytes: &[u8]) -> Result<(), usize> {
+    match bytes.iter().position(|byte| !byte.is_ascii_graphic()) {
+        _ if bytes.is_empty() => Err(0),
+        Some(offset) => Err(offset),
+        None => Ok(()),
+    }
+}
+/// Validates that `bytes` is a URI `scheme` (see RFC 3986, section 3.1)
+pub fn validate_scheme(bytes: &[u8]) -> Result<(), usize> {
+    let is_valid = |(index, byte): (usize, &u8)| match index {
+        0 => byte.is_ascii_alphabetic(),
+        _ => byte.is_ascii_alphanumeric() || matches!(byte, b'+' | b'-' | b'.'),
+    };
+    match bytes.iter().enumerate().position(|pair| !is_valid(pair)) {
+        _ if bytes.is_empty() => Err(0),
+        Some(offset) => Err(offset),
+        None => Ok(()),
+    }
+}
+/// Validates that `bytes` is a three-digit `status-code` (see RFC 9112, section 4)
+pub fn validate_status_code(bytes: &[u8]) -> Result<(), usize> {
+    match bytes.iter().position(|byte| !byte.is_ascii_digit()) {
+        Some(offset) => Err(offset),
+        None if bytes.len() != 3 => Err(bytes.len().min(3)),
+        None => Ok(()),
+    }
+}
+/// Validates that `bytes` is a `reason-phrase` (see RFC 9112, section 4)
+pub fn validate_reason_phrase(bytes: &[u8]) -> Result<(), usize> {
+    match bytes.iter().position(|byte| !is_field_vchar(*byte) && !is_ows(*byte)) {
+        Some(offset) => Err(offset),
+        None => Ok(()),
+    }
+}
+/// Validates that `bytes` is an `HTTP-version` of the form `HTTP/x.y` (see RFC 9112, section 2.3)
+pub fn validate_http_version(bytes: &[u8]) -> Result<(), usize> {
+    // Match the bytes against the pattern
+    let is_valid = |(index, byte): (usize, &u8)| match index {
+        5 | 7 => byte.is_ascii_digit(),
+        index => b"HTTP/x.y".get(index) == Some(byte),
+    };
+    match bytes.iter().enumerate().position(|pair| !is_valid(pair)) {
+        Some(offset) => Err(offset),
+        None if bytes.len() != 8 => Err(bytes.len()),
+        None => Ok(()),
+    }
+}
+
+/// Tests whether `byte` is a `qdtext` (see RFC 9110, section 5.6.4)
+pub const fn is_qdtext(byte: u8) -> bool {
+    matches!(byte, b'\t' | b' ' | 0x21 | 0x23..=0x5B | 0x5D..=0x7E | 0x80..=0xFF)
+}
+/// Tests whether `byte` is a `ctext` (see RFC 9110, section 5.6.5)
+pub const fn is_ctext(byte: u8) -> bool {
+    matches!(byte, b'\t' | b' ' | 0x21..=0x27 | 0x2A..=0x5B | 0x5D..=0x7E | 0x80..=0xFF)
+}
+/// Parses a `quoted-string` at the start of `bytes` and returns the unescaped content and the number of consumed bytes
+/// (see RFC 9110, section 5.6.4)
+pub fn parse_quoted_string(bytes: &[u8]) -> Result<(Vec<u8>, usize), usize> {
+    if bytes.first() != Some(&b'"') {
+        return Err(0);
+    }
+
+    // Unescape the content until the closing quote
+    let (mut content, mut offset) = (Vec::new(), 1);
+    while let Some(byte) = bytes.get(offset) {
+        match byte {
+            b'"' => return Ok((content, offset + 1)),
+            b'\\' => match bytes.get(offset + 1) {
+                Some(escaped) if is_field_vchar(*escaped) || is_ows(*escaped) => {
+                    content.push(*escaped);
+                    offset += 2;
+                }
+                _ => return Err(offset + 1),
+            },
+            byte if is_qdtext(*byte) => {
+                content.push(*byte);
+                offset += 1;
+            }
+            _ => return Err(offset),
+        }
+    }
+    Err(offset)
+}
+/// Serializes `value` as `token` if possible or as escaped `quoted-string` otherwise (see RFC 9110, section 5.6.4)
+///
+/// ## Note
+/// `value` must only consist of `field-vchar` or whitespace characters.
+pub fn write_token_or_quoted_string(value: &[u8], output: &mut Vec<u8>) {
+    if validate_token(value).is_ok() {
+        output.extend_from_slice(value);
+        return;
+    }
+
+    // Quote the value and escape quotes and backslashes
+    output.push(b'"');
+    for byte in value {
+        if matches!(byte, b'"' | b'\\') {
+            output.push(b'\\');
+        }
+        output.push(*byte);
+    }
+    output.push(b'"');
+}
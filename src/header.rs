@@ -1,32 +1,57 @@
 //! A HTTP 1/\*-header implementation
 
 use crate::{
+    body::Framing,
     bytetraits::{AsBytes, IntoBytes},
-    iotraits::{
-        BufReadExt,
-        MatchConfig::{Required, Trim},
-    },
+    date::HttpDate,
+    error::Error,
+    grammar,
+    headerref::{self, HeaderStartLineRef},
+    iotraits::{BufReadExt, MatchConfig::Required},
+    method::Method,
+    negotiation::{Negotiate, QValue},
+    options::{Limit, ParseMode, ReadOptions},
+    parser::{self, HeaderParser, Status},
+    rules::ListElements,
+    target::RequestTarget,
+    typed::{ContentLength, TransferEncoding, TypedHeader},
+    version::Version,
 };
 use std::{
     borrow::Cow,
-    collections::BTreeMap,
-    io::{BufRead, BufReader, Error, Write},
-    iter::FromIterator,
+    cmp::Ordering,
+    io::{self, BufRead, Read, Write},
+    iter::{self, FromIterator},
     ops::Deref,
+    vec,
 };
 
 /// A HTTP header
-#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
+///
+/// ## Note
+/// A parsed header retains the original layout of its start line and fields, so that it is written back
+/// byte-identically (see [`HeaderFields::write_all`]); this layout is not considered by comparisons.
+#[derive(Debug, Clone)]
 pub struct Header {
     /// The start line
     start_line: HeaderStartLine,
+    /// The original layout of the start line
+    layout: StartLineLayout,
     /// The header fields
     fields: HeaderFields,
 }
 impl Header {
     /// Creates a new HTTP/1.1 header
     pub const fn new(start_line: HeaderStartLine, fields: HeaderFields) -> Self {
-        Self { start_line, fields }
+        Self { start_line, layout: StartLineLayout::CANONICAL, fields }
+    }
+    /// Creates a new header that retains the original `layout` of the parsed start line
+    pub(crate) const fn with_layout(
+        start_line: HeaderStartLine,
+        layout: StartLineLayout,
+        fields: HeaderFields,
+    ) -> Self {
+        Self { start_line, layout, fields }
     }
 
     /// The start line
@@ -38,6 +63,36 @@ impl Header {
         &mut self.start_line
     }
 
+    /// Whether the header is a request header or not
+    pub fn is_request(&self) -> bool {
+        self.start_line.is_request()
+    }
+    /// Whether the header is a response header or not
+    pub fn is_response(&self) -> bool {
+        self.start_line.is_response()
+    }
+
+    /// Whether the connection should be kept alive after this message or not (see RFC 9112, section 9.3)
+    ///
+    /// ## Note
+    /// The `close` and `keep-alive` connection options override the default behavior of the HTTP version; messages with
+    /// an unsupported version never keep the connection alive.
+    pub fn is_keep_alive(&self) -> bool {
+        // Scan the connection options
+        let (mut close, mut keep_alive) = (false, false);
+        for option in self.fields.get_list("connection") {
+            close |= option.eq_ignore_ascii_case(b"close");
+            keep_alive |= option.eq_ignore_ascii_case(b"keep-alive");
+        }
+
+        // Apply the options to the default behavior
+        match self.start_line.version_typed() {
+            _ if close => false,
+            Ok(version) => keep_alive || version.is_keep_alive_default(),
+            Err(_) => false,
+        }
+    }
+
     /// The header fields
     pub fn fields(&self) -> &HeaderFields {
         &self.fields
@@ -47,33 +102,172 @@ impl Header {
         &mut self.fields
     }
 
-    /// Loads a HTTP header from `bytes` and returns the remaining bytes
+    /// Selects the best of the `available` items according to the preference field `T` of this request (e.g.
+    /// [`Accept`](crate::Accept)); returns `None` if no item is acceptable, which may be answered with
+    /// `406 Not Acceptable` (see RFC 9110, section 15.5.7)
+    ///
+    /// ## Note
+    /// If the field is absent, every item is acceptable. Items with equal quality are selected in the order of
+    /// `available`, so the most preferred representation of the server should come first.
+    pub fn negotiate<'a, T, I>(&self, available: I) -> Result<Option<&'a T::Item>, Error>
+    where
+        T: Negotiate,
+        I: IntoIterator<Item = &'a T::Item>,
+        T::Item: 'a,
+    {
+        let preferences = self.fields.typed_get::<T>()?;
+        let mut best: Option<(&T::Item, QValue)> = None;
+        for item in available {
+            // Keep the first item with the highest non-zero quality
+            let quality = preferences.as_ref().map_or(QValue::ONE, |preferences| preferences.quality(item));
+            if quality > QValue::ZERO && best.is_none_or(|(_, best_quality)| quality > best_quality) {
+                best = Some((item, quality));
+            }
+        }
+        Ok(best.map(|(item, _)| item))
+    }
+
+    /// Loads a HTTP header from `source` without any limits (see [`ReadOptions::unlimited`])
     ///
     /// # Warning:
     /// This function will read forever until there is either a `\r\n\r\n` or an EOF. To prevent DOS-attacks, it is
-    /// strongly recommended to wrap the `source` into a [`std::io::Take`] to limit the amount of data that will be read.
+    /// strongly recommended to use [`Header::read_with`] with sensible limits (e.g. [`ReadOptions::new`]), or to wrap the
+    /// `source` into a [`std::io::Take`] to limit the amount of data that will be read.
     pub fn read<T>(source: &mut T) -> Result<Self, Error>
     where
         T: BufRead,
     {
-        // Read the entire header
-        let header = source.read_word("\r\n\r\n", [Required])?;
-        let mut header = header.as_slice();
-        let mut header = BufReader::new(&mut header);
+        Self::read_with(source, &ReadOptions::unlimited())
+    }
+    /// Loads a HTTP header from `source` using the given `options`
+    ///
+    /// ## Note
+    /// If a limit is exceeded, [`Error::LimitExceeded`] is returned with the exceeded [`Limit`].
+    pub fn read_with<T>(source: &mut T, options: &ReadOptions) -> Result<Self, Error>
+    where
+        T: BufRead,
+    {
+        // Read the start line
+        let (line, offset) = Self::read_line(source, options, Limit::StartLine, 0)?;
+        let (start_line, is_simple_request) = HeaderStartLine::parse(&line, 0, options)?;
+        let layout = StartLineLayout::new(&line, line_break(line.len(), offset), is_simple_request);
+        if is_simple_request {
+            return Ok(Self { start_line, layout, fields: HeaderFields::new() });
+        }
 
-        // Read start line and fields
-        let start_line = HeaderStartLine::read(&mut header)?;
-        let fields = HeaderFields::read(&mut header)?;
-        Ok(Self { start_line, fields })
+        // Read the field lines until the end of the header
+        let fields = HeaderFields::read_at(source, options, offset)?;
+        Ok(Self { start_line, layout, fields })
+    }
+    /// Parses a HTTP header from `buf` using the default [`ReadOptions`]
+    ///
+    /// Returns the header together with the amount of consumed bytes, or [`Status::Partial`] if `buf` does not contain
+    /// a complete header yet. To parse data that arrives in chunks without re-scanning the buffer, see [`HeaderParser`].
+    pub fn parse(buf: &[u8]) -> Result<Status<(Self, usize)>, Error> {
+        Self::parse_with(buf, &ReadOptions::default())
+    }
+    /// Parses a HTTP header from `buf` using the given `options`
+    ///
+    /// Returns the header together with the amount of consumed bytes, or [`Status::Partial`] if `buf` does not contain
+    /// a complete header yet.
+    pub fn parse_with(buf: &[u8], options: &ReadOptions) -> Result<Status<(Self, usize)>, Error> {
+        HeaderParser::with_options(*options).parse(buf)
     }
+
     /// Writes the HTTP header
-    pub fn write_all(&self, output: &mut dyn Write) -> Result<(), Error> {
-        self.start_line.write_all(output)?;
-        self.fields.write_all(output)?;
+    ///
+    /// ## Note
+    /// A simple HTTP/0.9 request (i.e. a parsed simple request line or a created `HTTP/0.9` request) is written without
+    /// version, header fields and the terminating empty line (e.g. `GET /index.html\r\n`); a simple request with header
+    /// fields is rejected with [`io::ErrorKind::InvalidInput`].
+    pub fn write_all(&self, output: &mut dyn Write) -> Result<(), io::Error> {
+        let is_simple_request = self.layout.is_simple_request(&self.start_line);
+        if is_simple_request && !self.fields.is_empty() {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "HTTP/0.9 requests cannot have header fields"));
+        }
+
+        self.start_line.write_line(output, &self.layout)?;
+        if !is_simple_request {
+            self.fields.write_all(output)?;
+        }
         output.flush()?;
         Ok(())
     }
 
+    /// Determines the framing of the message body (see RFC 9112, section 6.3)
+    ///
+    /// For responses, `request_method` is the method of the corresponding request if known; this is necessary to
+    /// detect responses to `HEAD` and `CONNECT` requests.
+    ///
+    /// ## Note
+    /// A message with both `Transfer-Encoding` and `Content-Length` is rejected with
+    /// [`Error::InvalidTransferEncoding`], since it is a common vector for request smuggling. The same applies to a
+    /// request whose final transfer coding is not `chunked`, and to a `Transfer-Encoding` in a message that is not
+    /// HTTP/1.1 (see RFC 9112, section 6.1). A response whose final transfer coding is not `chunked` is read until
+    /// the connection is closed.
+    pub fn body_framing(&self, request_method: Option<&[u8]>) -> Result<Framing, Error> {
+        // Handle the responses without body
+        if let HeaderStartLine::Response { status, .. } = &self.start_line {
+            let is_2xx = status.starts_with(b"2");
+            match (request_method, status.as_ref()) {
+                (Some(b"HEAD"), _) | (_, [b'1', _, _] | b"204" | b"304") => return Ok(Framing::None),
+                (Some(b"CONNECT"), _) if is_2xx => return Ok(Framing::None),
+                _ => (/* the response may have a body */),
+            }
+        }
+
+        // Parse the framing fields
+        let is_chunked = match self.is_request() {
+            true => self.fields.is_chunked()?,
+            false => {
+                let values: Vec<_> = self.fields.get_all(TransferEncoding::NAME).collect();
+                let transfer_encoding = (!values.is_empty()).then(|| TransferEncoding::decode_unordered(&values));
+                transfer_encoding.transpose()?.map(|transfer_encoding| transfer_encoding.is_chunked())
+            }
+        };
+        if is_chunked.is_some() && !matches!(self.start_line.version_typed(), Ok(Version::Http11)) {
+            return Err(Error::InvalidTransferEncoding);
+        }
+        let content_length = self.fields.content_length()?;
+        match (is_chunked, content_length) {
+            (Some(_), Some(_)) => Err(Error::InvalidTransferEncoding),
+            (Some(true), None) => Ok(Framing::Chunked),
+            (Some(false), None) if self.is_request() => Err(Error::InvalidTransferEncoding),
+            (Some(false), None) => Ok(Framing::UntilClose),
+            (None, Some(length)) => Ok(Framing::ContentLength(length)),
+            (None, None) if self.is_request() => Ok(Framing::None),
+            (None, None) => Ok(Framing::UntilClose),
+        }
+    }
+
+    /// Reads a single line of type `limit` at `offset` from `source` and returns the line (excluding the line break)
+    /// together with the raw line length
+    fn read_line<T>(
+        source: &mut T,
+        options: &ReadOptions,
+        limit: Limit,
+        offset: usize,
+    ) -> Result<(Vec<u8>, usize), Error>
+    where
+        T: BufRead,
+    {
+        // Read the line
+        let (max_len, window_limit) = options.line_limit(limit, offset);
+        let mut source = source.take(u64::try_from(max_len).unwrap_or(u64::MAX));
+        let mut line = source.read_word("\n", [])?;
+        match line.ends_with(b"\n") {
+            true => (),
+            false if line.len() == max_len => return Err(Error::LimitExceeded { limit: window_limit, offset }),
+            false => return Err(Error::Incomplete),
+        }
+
+        // Strip the line break
+        let raw_len = line.len();
+        let stripped_len = parser::strip_line_break(&line, options, limit, offset)?.len();
+        line.truncate(stripped_len);
+        Ok((line, raw_len))
+    }
+
     /// Tests whether `buf` contains a complete header
     ///
     /// ## Note
@@ -87,31 +281,202 @@ impl Header {
     }
 }
 
+impl PartialEq for Header {
+    fn eq(&self, other: &Self) -> bool {
+        (&self.start_line, &self.fields) == (&other.start_line, &other.fields)
+    }
+}
+impl Eq for Header {}
+impl PartialOrd for Header {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for Header {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (&self.start_line, &self.fields).cmp(&(&other.start_line, &other.fields))
+    }
+}
+
+/// The original layout of a parsed start line
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct StartLineLayout {
+    /// The line break
+    line_break: &'static [u8],
+    /// Whether an empty reason phrase is preceded by a space or not
+    reason_separator: bool,
+    /// Whether the start line has been parsed as simple HTTP/0.9 request line or not, or `None` if it has been created
+    simple_request: Option<bool>,
+}
+impl StartLineLayout {
+    /// The canonical layout
+    const CANONICAL: Self = Self { line_break: b"\r\n", reason_separator: true, simple_request: None };
+
+    /// The canonical layout of a parsed start line
+    pub(crate) const fn canonical(is_simple_request: bool) -> Self {
+        Self { simple_request: Some(is_simple_request), ..Self::CANONICAL }
+    }
+    /// Gets the layout of the start `line` (excluding the `line_break`)
+    pub(crate) fn new(line: &[u8], line_break: &'static [u8], is_simple_request: bool) -> Self {
+        let reason_separator = line.iter().filter(|b| **b == b' ').count() >= 2;
+        Self { line_break, reason_separator, simple_request: Some(is_simple_request) }
+    }
+
+    /// Whether `start_line` is written as simple HTTP/0.9 request line or not
+    fn is_simple_request(&self, start_line: &HeaderStartLine) -> bool {
+        self.simple_request.unwrap_or_else(|| start_line.is_request() && start_line.is_http09())
+    }
+}
+
 /// The start line
 #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
-pub struct HeaderStartLine {
-    field0: Cow<'static, [u8]>,
-    field1: Cow<'static, [u8]>,
-    field2: Cow<'static, [u8]>,
+pub enum HeaderStartLine {
+    /// A request line (see RFC 9112, section 3)
+    Request {
+        /// The request method
+        method: Cow<'static, [u8]>,
+        /// The request target
+        target: Cow<'static, [u8]>,
+        /// The HTTP version
+        version: Cow<'static, [u8]>,
+    },
+    /// A status line (see RFC 9112, section 4)
+    Response {
+        /// The HTTP version
+        version: Cow<'static, [u8]>,
+        /// The status code
+        status: Cow<'static, [u8]>,
+        /// The reason phrase
+        reason: Cow<'static, [u8]>,
+    },
 }
 impl HeaderStartLine {
     /// Creates a new HTTP/1.1 request
+    ///
+    /// # Panics
+    /// In debug builds, this function panics if `method` is not a valid token or `target` is not a valid request target;
+    /// use [`HeaderStartLine::try_new_request`] to validate untrusted input.
     pub fn new_request<T, U>(method: T, target: U) -> Self
     where
         T: IntoBytes,
         U: IntoBytes,
     {
-        Self { field0: method.into_bytes(), field1: target.into_bytes(), field2: "HTTP/1.1".into_bytes() }
+        Self::new_request_with_version(method, target, Version::Http11)
+    }
+    /// Creates a new request with the given `version`
+    ///
+    /// ## Note
+    /// A [`Version::Http09`] request is serialized as simple request line without version (e.g. `GET /index.html`).
+    ///
+    /// # Panics
+    /// In debug builds, this function panics if `method` is not a valid token or `target` is not a valid request target;
+    /// use [`HeaderStartLine::try_new_request`] to validate untrusted input.
+    pub fn new_request_with_version<T, U>(method: T, target: U, version: Version) -> Self
+    where
+        T: IntoBytes,
+        U: IntoBytes,
+    {
+        let (method, target) = (method.into_bytes(), target.into_bytes());
+        debug_assert!(version.has_fields() || *method == *b"GET", "HTTP/0.9 requests must use the GET method");
+        debug_assert!(grammar::validate_token(&method).is_ok(), "invalid HTTP method");
+        debug_assert!(grammar::validate_request_target(&target).is_ok(), "invalid HTTP request target");
+        Self::Request { method, target, version: version.into_bytes() }
+    }
+    /// Creates a new HTTP/1.1 request or fails if `method` is not a valid token or `target` is not a valid request
+    /// target
+    ///
+    /// ## Note
+    /// The offset of the returned [`Error::InvalidStartLine`] is relative to the serialized start line.
+    pub fn try_new_request<T, U>(method: T, target: U) -> Result<Self, Error>
+    where
+        T: IntoBytes,
+        U: IntoBytes,
+    {
+        Self::try_new_request_with_version(method, target, Version::Http11)
+    }
+    /// Creates a new request with the given `version` or fails if `method` is not a valid token or `target` is not a
+    /// valid request target
+    ///
+    /// ## Note
+    /// The offset of the returned [`Error::InvalidStartLine`] is relative to the serialized start line.
+    pub fn try_new_request_with_version<T, U>(method: T, target: U, version: Version) -> Result<Self, Error>
+    where
+        T: IntoBytes,
+        U: IntoBytes,
+    {
+        let (method, target) = (method.into_bytes(), target.into_bytes());
+        grammar::validate_token(&method).map_err(|offset| Error::InvalidStartLine { offset })?;
+        if !version.has_fields() && *method != *b"GET" {
+            return Err(Error::InvalidStartLine { offset: 0 });
+        }
+        grammar::validate_request_target(&target)
+            .map_err(|offset| Error::InvalidStartLine { offset: method.len() + 1 + offset })?;
+        Ok(Self::Request { method, target, version: version.into_bytes() })
     }
     /// Creates a new HTTP/1.1 response
+    ///
+    /// ## Note
+    /// The `reason` may be empty (e.g. `HeaderStartLine::new_response(204, "")`); the serialized status line retains the
+    /// space after the status code nevertheless, as required by RFC 9112.
+    ///
+    /// # Panics
+    /// This function panics if `status` is not numeric. In debug builds, this function also panics if `reason` is not a
+    /// valid reason phrase; use [`HeaderStartLine::try_new_response`] to validate untrusted input.
     pub fn new_response<T, U>(status: T, reason: U) -> Self
     where
         T: IntoBytes,
         U: IntoBytes,
     {
-        let status = status.into_bytes();
+        Self::new_response_with_version(Version::Http11, status, reason)
+    }
+    /// Creates a new response with the given `version`
+    ///
+    /// # Panics
+    /// This function panics if `version` is [`Version::Http09`] (which has no status line) or if `status` is not
+    /// numeric. In debug builds, this function also panics if `reason` is not a valid reason phrase; use
+    /// [`HeaderStartLine::try_new_response_with_version`] to validate untrusted input.
+    pub fn new_response_with_version<T, U>(version: Version, status: T, reason: U) -> Self
+    where
+        T: IntoBytes,
+        U: IntoBytes,
+    {
+        let (status, reason) = (status.into_bytes(), reason.into_bytes());
+        assert!(version.has_fields(), "HTTP/0.9 responses have no status line");
         assert!(status.iter().all(u8::is_ascii_digit), "non-numeric HTTP status code");
-        Self { field0: "HTTP/1.1".into_bytes(), field1: status, field2: reason.into_bytes() }
+        debug_assert!(grammar::validate_reason_phrase(&reason).is_ok(), "invalid HTTP reason phrase");
+        Self::Response { version: version.into_bytes(), status, reason }
+    }
+    /// Creates a new HTTP/1.1 response or fails if `status` is not a three-digit status code or `reason` is not a valid
+    /// reason phrase
+    ///
+    /// ## Note
+    /// The offset of the returned [`Error::InvalidStartLine`] is relative to the serialized start line.
+    pub fn try_new_response<T, U>(status: T, reason: U) -> Result<Self, Error>
+    where
+        T: IntoBytes,
+        U: IntoBytes,
+    {
+        Self::try_new_response_with_version(Version::Http11, status, reason)
+    }
+    /// Creates a new response with the given `version` or fails if `version` is [`Version::Http09`] (which has no
+    /// status line), `status` is not a three-digit status code or `reason` is not a valid reason phrase
+    ///
+    /// ## Note
+    /// The offset of the returned [`Error::InvalidStartLine`] is relative to the serialized start line.
+    pub fn try_new_response_with_version<T, U>(version: Version, status: T, reason: U) -> Result<Self, Error>
+    where
+        T: IntoBytes,
+        U: IntoBytes,
+    {
+        if !version.has_fields() {
+            return Err(Error::InvalidStartLine { offset: 0 });
+        }
+        let (version, status, reason) = (version.into_bytes(), status.into_bytes(), reason.into_bytes());
+        grammar::validate_status_code(&status)
+            .map_err(|offset| Error::InvalidStartLine { offset: version.len() + 1 + offset })?;
+        grammar::validate_reason_phrase(&reason)
+            .map_err(|offset| Error::InvalidStartLine { offset: version.len() + status.len() + 2 + offset })?;
+        Ok(Self::Response { version, status, reason })
     }
 
     /// Reads the start line from `source`
@@ -119,135 +484,520 @@ impl HeaderStartLine {
     where
         T: BufRead,
     {
-        // Read the start line
-        let line = source.read_word("\r\n", [Required, Trim])?;
-        let mut line = line.as_slice();
-        let mut line = BufReader::new(&mut line);
-
-        // Split the start line into its fields
-        let this = Self {
-            field0: line.read_word(" ", [Required, Trim])?.into_bytes(),
-            field1: line.read_word(" ", [Required, Trim])?.into_bytes(),
-            field2: line.read_all([Required])?.into_bytes(),
-        };
-        Ok(this)
+        let line = source.read_word("\n", [Required])?;
+        let line = parser::strip_line_break(&line, &ReadOptions::unlimited(), Limit::StartLine, 0)?;
+        let (start_line, _) = Self::parse(line, 0, &ReadOptions::unlimited())?;
+        Ok(start_line)
+    }
+    /// Parses the start line at `offset` from `line` (excluding the line break) together with whether it is a simple
+    /// HTTP/0.9 request line or not
+    pub(crate) fn parse(line: &[u8], offset: usize, options: &ReadOptions) -> Result<(Self, bool), Error> {
+        let (start_line, is_simple_request) = HeaderStartLineRef::parse(line, offset, options)?;
+        Ok((start_line.to_owned(), is_simple_request))
+    }
+    /// Whether the version is `HTTP/0.9` or not
+    fn is_http09(&self) -> bool {
+        self.version() == Version::Http09.as_bytes()
     }
     /// Writes the HTTP start line
-    pub fn write_all(&self, output: &mut dyn Write) -> Result<(), Error> {
-        output.write_all(&self.field0)?;
-        output.write_all(b" ")?;
-        output.write_all(&self.field1)?;
+    ///
+    /// ## Note
+    /// A `HTTP/0.9` request line is written as simple request line without version (e.g. `GET /index.html\r\n`); a
+    /// `HTTP/0.9` status line is rejected with [`io::ErrorKind::InvalidInput`], since HTTP/0.9 responses have none.
+    pub fn write_all(&self, output: &mut dyn Write) -> Result<(), io::Error> {
+        self.write_line(output, &StartLineLayout::CANONICAL)
+    }
+    /// Writes the HTTP start line with the given `layout`
+    fn write_line(&self, output: &mut dyn Write, layout: &StartLineLayout) -> Result<(), io::Error> {
+        match self {
+            Self::Request { method, target, .. } if layout.is_simple_request(self) => {
+                output.write_all(method)?;
+                output.write_all(b" ")?;
+                output.write_all(target)?;
+                output.write_all(layout.line_break)?;
+                return Ok(());
+            }
+            Self::Response { .. } if layout.simple_request.is_none() && self.is_http09() => {
+                return Err(io::Error::new(io::ErrorKind::InvalidInput, "HTTP/0.9 responses have no status line"));
+            }
+            _ => (),
+        }
+        output.write_all(self.field0())?;
         output.write_all(b" ")?;
-        output.write_all(&self.field2)?;
-        output.write_all(b"\r\n")?;
+        output.write_all(self.field1())?;
+        if self.is_request() || !self.field2().is_empty() || layout.reason_separator {
+            output.write_all(b" ")?;
+        }
+        output.write_all(self.field2())?;
+        output.write_all(layout.line_break)?;
         Ok(())
     }
 
+    /// Whether the start line is a request line or not
+    pub fn is_request(&self) -> bool {
+        matches!(self, Self::Request { .. })
+    }
+    /// Whether the start line is a status line or not
+    pub fn is_response(&self) -> bool {
+        matches!(self, Self::Response { .. })
+    }
+
+    /// The request method if the start line is a request line
+    pub fn method(&self) -> Option<&[u8]> {
+        match self {
+            Self::Request { method, .. } => Some(method),
+            Self::Response { .. } => None,
+        }
+    }
+    /// The request target if the start line is a request line
+    pub fn target(&self) -> Option<&[u8]> {
+        match self {
+            Self::Request { target, .. } => Some(target),
+            Self::Response { .. } => None,
+        }
+    }
+    /// The request target as [`RequestTarget`] if the start line is a request line
+    ///
+    /// ## Note
+    /// The form of the target is validated against the request method; see [`RequestTarget::parse`].
+    pub fn target_typed(&self) -> Result<RequestTarget<'_>, Error> {
+        match self {
+            Self::Request { method, target, .. } => RequestTarget::parse(target, &Method::parse(method)?),
+            Self::Response { .. } => Err(Error::InvalidStartLine { offset: 0 }),
+        }
+    }
+    /// The HTTP version
+    pub fn version(&self) -> &[u8] {
+        match self {
+            Self::Request { version, .. } | Self::Response { version, .. } => version,
+        }
+    }
+    /// The HTTP version as [`Version`]
+    pub fn version_typed(&self) -> Result<Version, Error> {
+        Version::parse(self.version())
+    }
+    /// The status code if the start line is a status line
+    pub fn status(&self) -> Option<&[u8]> {
+        match self {
+            Self::Response { status, .. } => Some(status),
+            Self::Request { .. } => None,
+        }
+    }
+    /// The reason phrase if the start line is a status line
+    pub fn reason(&self) -> Option<&[u8]> {
+        match self {
+            Self::Response { reason, .. } => Some(reason),
+            Self::Request { .. } => None,
+        }
+    }
+
     /// The first field of the header line (i.e. either "method" for requests or "version" for response)
     pub fn field0(&self) -> &[u8] {
-        self.field0.as_ref()
+        match self {
+            Self::Request { method: field0, .. } | Self::Response { version: field0, .. } => field0,
+        }
     }
     /// The first field of the header line (i.e. either "method" for requests or "version" for response)
     pub fn field0_mut(&mut self) -> &mut Cow<'static, [u8]> {
-        &mut self.field0
+        match self {
+            Self::Request { method: field0, .. } | Self::Response { version: field0, .. } => field0,
+        }
     }
 
     /// The second field of the header line (i.e. either "target" for requests or "status" for response)
     pub fn field1(&self) -> &[u8] {
-        self.field1.as_ref()
+        match self {
+            Self::Request { target: field1, .. } | Self::Response { status: field1, .. } => field1,
+        }
     }
     /// The second field of the header line (i.e. either "target" for requests or "status" for response)
     pub fn field1_mut(&mut self) -> &mut Cow<'static, [u8]> {
-        &mut self.field1
+        match self {
+            Self::Request { target: field1, .. } | Self::Response { status: field1, .. } => field1,
+        }
     }
 
     /// The third field of the header line (i.e. either "version" for requests or "reason" for response)
     pub fn field2(&self) -> &[u8] {
-        self.field2.as_ref()
+        match self {
+            Self::Request { version: field2, .. } | Self::Response { reason: field2, .. } => field2,
+        }
     }
     /// The third field of the header line (i.e. either "version" for requests or "reason" for response)
     pub fn field2_mut(&mut self) -> &mut Cow<'static, [u8]> {
-        &mut self.field2
+        match self {
+            Self::Request { version: field2, .. } | Self::Response { reason: field2, .. } => field2,
+        }
+    }
+}
+
+/// A header field as name-value pair
+type HeaderField = (Cow<'static, [u8]>, Cow<'static, [u8]>);
+/// The original bytes between name and value (e.g. `: `) and after the value including the line break (e.g. `\r\n`)
+type FieldLayout = (Box<[u8]>, Box<[u8]>);
+
+/// A stored header field
+///
+/// ## Note
+/// This type is public only since it is part of [`HeaderFields::into_iter`]; it is not exported by the crate.
+#[derive(Debug, Clone)]
+pub struct Field {
+    /// The field name
+    name: Cow<'static, [u8]>,
+    /// The field value without surrounding whitespace
+    value: Cow<'static, [u8]>,
+    /// The original layout if the field has been parsed
+    layout: Option<FieldLayout>,
+}
+impl Field {
+    /// Creates a new field that is serialized in the canonical `name: value` form
+    fn new(name: Cow<'static, [u8]>, value: Cow<'static, [u8]>) -> Self {
+        Self { name, value, layout: None }
     }
 }
 
 /// Some header fields
-#[derive(Debug, Default, Clone, Eq, PartialEq, Ord, PartialOrd)]
+///
+/// ## Note
+/// A field name may occur multiple times (e.g. `Set-Cookie`); all occurrences are retained in the order they have been
+/// added. Field names keep their original casing, but are always matched case-insensitive. Parsed fields also retain
+/// their original whitespace and line breaks, so that they are written back byte-identically; this layout is not
+/// considered by comparisons.
+#[derive(Debug, Default, Clone)]
 pub struct HeaderFields {
-    /// The header fields
-    fields: BTreeMap<Cow<'static, [u8]>, Cow<'static, [u8]>>,
+    /// The header fields in their order of occurrence
+    fields: Vec<Field>,
+    /// The original line break of the empty line that terminates the fields if the fields have been parsed
+    end: Option<&'static [u8]>,
 }
 impl HeaderFields {
     /// Creates a new header field map
     pub fn new() -> Self {
-        Self { fields: BTreeMap::new() }
+        Self { fields: Vec::new(), end: None }
     }
 
-    /// Gets the value for the field with the given name
-    pub fn get<T>(&self, name: T) -> Option<&[u8]>
+    /// Gets the first value for the field with the given name
+    pub fn get<'a, T>(&self, name: T) -> Option<&[u8]>
+    where
+        T: AsBytes<'a>,
+    {
+        self.get_all(name).next()
+    }
+    /// Gets all values for the field with the given name in their order of occurrence
+    pub fn get_all<'a, T>(&self, name: T) -> impl Iterator<Item = &[u8]> + use<'_, 'a, T>
     where
-        T: for<'a> AsBytes<'a>,
+        T: AsBytes<'a>,
     {
-        let name = name.into_ascii_lowercase();
-        let value = self.fields.get(name.as_ref())?;
-        Some(value.deref())
+        let name = name.as_bytes();
+        let fields = self.fields.iter().filter(move |field| field.name.eq_ignore_ascii_case(name));
+        fields.map(|field| field.value.deref())
     }
-    /// Sets the value for a fiels with the given name
+    /// Gets the elements of the comma-separated list field with the given name across all of its occurrences (see
+    /// RFC 9110, section 5.6.1)
+    ///
+    /// ## Note
+    /// See [`ListElements`] for how the values are split.
+    pub fn get_list<'a, T>(&self, name: T) -> impl Iterator<Item = &[u8]> + use<'_, 'a, T>
+    where
+        T: AsBytes<'a>,
+    {
+        self.get_all(name).flat_map(ListElements::new)
+    }
+    /// Gets all values for the field with the given name combined into a single comma-separated value
+    ///
+    /// ## Note
+    /// Combining the values is only semantically correct for fields that are defined as comma-separated list (see
+    /// RFC 9110, section 5.3); this is explicitly not the case for `Set-Cookie`.
+    pub fn get_combined<'a, T>(&self, name: T) -> Option<Cow<'_, [u8]>>
+    where
+        T: AsBytes<'a>,
+    {
+        let mut values = self.get_all(name);
+        let first = values.next()?;
+
+        // Avoid the allocation if there is only a single value
+        let mut combined = match values.next() {
+            Some(second) => [first, b", ", second].concat(),
+            None => return Some(Cow::Borrowed(first)),
+        };
+        for value in values {
+            combined.extend_from_slice(b", ");
+            combined.extend_from_slice(value);
+        }
+        Some(Cow::Owned(combined))
+    }
+
+    /// Gets the value of the `Content-Length` field
+    ///
+    /// ## Note
+    /// Repeated fields and comma-separated lists with identical values are accepted as a single length (see RFC 9110,
+    /// section 8.6); any other combination, as well as a non-numeric value, is an [`Error::InvalidContentLength`].
+    pub fn content_length(&self) -> Result<Option<u64>, Error> {
+        let content_length = self.typed_get::<ContentLength>()?;
+        Ok(content_length.map(|ContentLength(length)| length))
+    }
+
+    /// Gets the first value of the field with the given name as HTTP date (e.g. for `Date`, `Last-Modified`, `Expires`
+    /// or `If-Modified-Since`)
+    ///
+    /// ## Note
+    /// A malformed date is an [`Error::InvalidFieldValue`] with an offset relative to the field value. Note that some
+    /// fields may legitimately carry other values (e.g. delay-seconds for `Retry-After`).
+    pub fn get_date<'a, T>(&self, name: T) -> Result<Option<HttpDate>, Error>
+    where
+        T: AsBytes<'a>,
+    {
+        self.get(name).map(HttpDate::parse).transpose()
+    }
+
+    /// Tests whether the final transfer coding of the `Transfer-Encoding` field is `chunked`, or returns `None` if the
+    /// field is absent
+    ///
+    /// ## Note
+    /// Applying `chunked` more than once or not as final transfer coding is an [`Error::InvalidTransferEncoding`].
+    pub fn is_chunked(&self) -> Result<Option<bool>, Error> {
+        let transfer_encoding = self.typed_get::<TransferEncoding>()?;
+        Ok(transfer_encoding.map(|transfer_encoding| transfer_encoding.is_chunked()))
+    }
+
+    /// Gets and decodes a typed field, or returns `None` if the field is absent
+    pub fn typed_get<T>(&self) -> Result<Option<T>, Error>
+    where
+        T: TypedHeader,
+    {
+        let values: Vec<_> = self.get_all(T::NAME).collect();
+        match values.is_empty() {
+            true => Ok(None),
+            false => T::decode(&values).map(Some),
+        }
+    }
+    /// Encodes and sets a typed field, replacing all existing occurrences
+    pub fn typed_set<T>(&mut self, field: T)
+    where
+        T: TypedHeader,
+    {
+        self.set_raw(Cow::Borrowed(T::NAME.as_bytes()), Cow::Owned(field.encode()));
+    }
+
+    /// Sets the value for a field with the given name, replacing all existing occurrences
+    ///
+    /// ## Note
+    /// If the field already exists, the new pair takes the position of the first occurrence.
+    ///
+    /// # Panics
+    /// In debug builds, this function panics if `name` is not a valid token or `value` is not a valid field value; use
+    /// [`HeaderFields::try_set`] to validate untrusted input.
     pub fn set<A, B>(&mut self, name: A, value: B)
     where
         A: IntoBytes,
         B: IntoBytes,
     {
-        let name = name.into_ascii_lowercase();
-        self.fields.insert(name, value.into_bytes());
+        let (name, value) = (name.into_bytes(), value.into_bytes());
+        debug_assert!(Self::validate(&name, &value).is_ok(), "invalid HTTP header field");
+        self.set_raw(name, value);
+    }
+    /// Sets the value for a field with the given name, replacing all existing occurrences, or fails if `name` is not a
+    /// valid token or `value` is not a valid field value
+    ///
+    /// ## Note
+    /// The offset of the returned error is relative to the invalid `name` or `value`.
+    pub fn try_set<A, B>(&mut self, name: A, value: B) -> Result<(), Error>
+    where
+        A: IntoBytes,
+        B: IntoBytes,
+    {
+        let (name, value) = (name.into_bytes(), value.into_bytes());
+        Self::validate(&name, &value)?;
+        self.set_raw(name, value);
+        Ok(())
+    }
+    /// Sets the value for a field with the given name without validation
+    fn set_raw(&mut self, name: Cow<'static, [u8]>, value: Cow<'static, [u8]>) {
+        match self.fields.iter().position(|field| field.name.eq_ignore_ascii_case(&name)) {
+            Some(index) => {
+                // Remove all subsequent occurrences and replace the first one
+                let mut position = 0;
+                self.fields.retain(|field| {
+                    position += 1;
+                    position <= index + 1 || !field.name.eq_ignore_ascii_case(&name)
+                });
+                self.fields[index] = Field::new(name, value);
+            }
+            None => self.fields.push(Field::new(name, value)),
+        }
+    }
+    /// Appends a value for a field with the given name, retaining all existing occurrences
+    ///
+    /// # Panics
+    /// In debug builds, this function panics if `name` is not a valid token or `value` is not a valid field value; use
+    /// [`HeaderFields::try_append`] to validate untrusted input.
+    pub fn append<A, B>(&mut self, name: A, value: B)
+    where
+        A: IntoBytes,
+        B: IntoBytes,
+    {
+        let (name, value) = (name.into_bytes(), value.into_bytes());
+        debug_assert!(Self::validate(&name, &value).is_ok(), "invalid HTTP header field");
+        self.fields.push(Field::new(name, value));
+    }
+    /// Appends a value for a field with the given name, retaining all existing occurrences, or fails if `name` is not a
+    /// valid token or `value` is not a valid field value
+    ///
+    /// ## Note
+    /// The offset of the returned error is relative to the invalid `name` or `value`.
+    pub fn try_append<A, B>(&mut self, name: A, value: B) -> Result<(), Error>
+    where
+        A: IntoBytes,
+        B: IntoBytes,
+    {
+        let (name, value) = (name.into_bytes(), value.into_bytes());
+        Self::validate(&name, &value)?;
+        self.fields.push(Field::new(name, value));
+        Ok(())
+    }
+    /// Appends a value for a field with the given name without validation
+    pub(crate) fn append_raw<A, B>(&mut self, name: A, value: B)
+    where
+        A: IntoBytes,
+        B: IntoBytes,
+    {
+        self.fields.push(Field::new(name.into_bytes(), value.into_bytes()));
+    }
+    /// Removes all occurrences of the field with the given name and returns the amount of removed values
+    pub fn remove_all<'a, T>(&mut self, name: T) -> usize
+    where
+        T: AsBytes<'a>,
+    {
+        let name = name.as_bytes();
+        let len = self.fields.len();
+        self.fields.retain(|field| !field.name.eq_ignore_ascii_case(name));
+        len - self.fields.len()
+    }
+
+    /// An iterator over all name-value pairs in their order of occurrence
+    pub fn iter(&self) -> impl Iterator<Item = (&[u8], &[u8])> {
+        self.fields.iter().map(|field| (field.name.deref(), field.value.deref()))
+    }
+    /// The amount of name-value pairs
+    pub fn len(&self) -> usize {
+        self.fields.len()
+    }
+    /// Whether there are no header fields or not
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
     }
 
     /// Reads the header fields from `source`
+    ///
+    /// # Warning:
+    /// Reading the fields without limits will read forever until there is either a `\r\n\r\n` or an EOF. To prevent
+    /// DOS-attacks, use [`HeaderFields::read_with`] or wrap the source into a [`std::io::Take`].
     pub fn read<T>(source: &mut T) -> Result<Self, Error>
+    where
+        T: BufRead,
+    {
+        Self::read_with(source, &ReadOptions::unlimited())
+    }
+    /// Reads the header fields from `source` using the given `options`
+    ///
+    /// ## Note
+    /// The fields are subject to the field line, field count and header size limits; the start line limit is ignored.
+    /// If a limit is exceeded, [`Error::LimitExceeded`] is returned with the exceeded [`Limit`].
+    pub fn read_with<T>(source: &mut T, options: &ReadOptions) -> Result<Self, Error>
+    where
+        T: BufRead,
+    {
+        Self::read_at(source, options, 0)
+    }
+    /// Reads the header fields that start at `offset` within the header from `source`
+    fn read_at<T>(source: &mut T, options: &ReadOptions, mut offset: usize) -> Result<Self, Error>
     where
         T: BufRead,
     {
         let mut this = HeaderFields::new();
         'read_lines: loop {
-            // Unwrap the next line and check for end of header
-            let line = source.read_word("\r\n", [Required, Trim])?;
-            let mut line = line.as_slice();
-            let mut line = match line {
-                line if line.is_empty() => break 'read_lines,
-                _ => BufReader::new(&mut line),
-            };
-
-            // Split the line into key-value
-            let key = line.read_word(":", [Required, Trim])?;
-            let mut value = line.read_all([Required])?;
-
-            // Trim the leading spaces
-            let leading_whitespace = value.iter().take_while(|b| **b == b' ').count();
-            value.copy_within(leading_whitespace.., 0);
-            value.truncate(value.len() - leading_whitespace);
-
-            // Insert the pair
-            this.set(key, value);
+            // Read the next line and check for end of header
+            let (line, raw_len) = Header::read_line(source, options, Limit::FieldLine, offset)?;
+            let line_break = line_break(line.len(), raw_len);
+            match line.as_slice() {
+                [] => {
+                    this.end = Some(line_break);
+                    break 'read_lines;
+                }
+                _ if this.len() >= options.max_field_count => {
+                    return Err(Error::LimitExceeded { limit: Limit::FieldCount, offset })
+                }
+                line => this.parse_line(line, line_break, offset, options.mode)?,
+            }
+            offset += raw_len;
         }
         Ok(this)
     }
+    /// Parses a header field line at `offset` from `line` (excluding the `line_break`) and appends the field together
+    /// with its original layout
+    pub(crate) fn parse_line(
+        &mut self,
+        line: &[u8],
+        line_break: &'static [u8],
+        offset: usize,
+        mode: ParseMode,
+    ) -> Result<(), Error> {
+        let (name, value) = headerref::parse_field_line(line, offset, mode)?;
+
+        // Retain the separator and the trailing whitespace (note: the name is directly followed by the colon)
+        let value_start = line[name.len() + 1..].iter().take_while(|b| grammar::is_ows(**b)).count() + name.len() + 1;
+        let separator = line[name.len()..value_start].into();
+        let terminator = [&line[value_start + value.len()..], line_break].concat().into();
+        let (name, value) = (Cow::Owned(name.to_vec()), Cow::Owned(value.to_vec()));
+        self.fields.push(Field { name, value, layout: Some((separator, terminator)) });
+        Ok(())
+    }
+    /// Sets the original line break of the empty line that terminates the parsed fields
+    pub(crate) fn set_end(&mut self, line_break: &'static [u8]) {
+        self.end = Some(line_break);
+    }
+    /// Validates that `name` is a valid token and `value` is a valid field value
+    fn validate(name: &[u8], value: &[u8]) -> Result<(), Error> {
+        grammar::validate_token(name).map_err(|offset| Error::InvalidFieldName { offset })?;
+        grammar::validate_field_value(value).map_err(|offset| Error::InvalidFieldValue { offset })?;
+        Ok(())
+    }
     /// Writes the HTTP header fields
-    pub fn write_all(&self, output: &mut dyn Write) -> Result<(), Error> {
-        for (key, value) in self.fields.iter() {
-            output.write_all(key)?;
-            output.write_all(b": ")?;
-            output.write_all(value)?;
-            output.write_all(b"\r\n")?;
-        }
-        output.write_all(b"\r\n")?;
+    ///
+    /// ## Note
+    /// The fields are written in their order of occurrence and with their original name casing. Parsed fields are
+    /// written with their original whitespace and line breaks, so that parsed fields are reproduced byte-identically;
+    /// all other fields (including fields that replaced a parsed field via [`HeaderFields::set`]) are written in the
+    /// canonical `name: value\r\n` form.
+    pub fn write_all(&self, output: &mut dyn Write) -> Result<(), io::Error> {
+        for field in &self.fields {
+            let (separator, terminator) = match &field.layout {
+                Some((separator, terminator)) => (separator.deref(), terminator.deref()),
+                None => (b": ".as_slice(), b"\r\n".as_slice()),
+            };
+            output.write_all(&field.name)?;
+            output.write_all(separator)?;
+            output.write_all(&field.value)?;
+            output.write_all(terminator)?;
+        }
+        output.write_all(self.end.unwrap_or(b"\r\n"))?;
         Ok(())
     }
 }
-impl Deref for HeaderFields {
-    type Target = BTreeMap<Cow<'static, [u8]>, Cow<'static, [u8]>>;
-
-    fn deref(&self) -> &Self::Target {
-        &self.fields
+impl PartialEq for HeaderFields {
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+impl Eq for HeaderFields {}
+impl PartialOrd for HeaderFields {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for HeaderFields {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
     }
 }
 impl<K, V> FromIterator<(K, V)> for HeaderFields
@@ -256,15 +1006,26 @@ where
     V: IntoBytes,
 {
     fn from_iter<T: IntoIterator<Item = (K, V)>>(pairs: T) -> Self {
-        let fields = pairs.into_iter().map(|(k, v)| (k.into_ascii_lowercase(), v.into_bytes())).collect();
-        Self { fields }
+        let mut this = Self::new();
+        for (name, value) in pairs {
+            this.append(name, value);
+        }
+        this
     }
 }
 impl IntoIterator for HeaderFields {
-    type Item = <BTreeMap<Cow<'static, [u8]>, Cow<'static, [u8]>> as IntoIterator>::Item;
-    type IntoIter = <BTreeMap<Cow<'static, [u8]>, Cow<'static, [u8]>> as IntoIterator>::IntoIter;
+    type Item = HeaderField;
+    type IntoIter = iter::Map<vec::IntoIter<Field>, fn(Field) -> HeaderField>;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.fields.into_iter()
+        self.fields.into_iter().map(|field| (field.name, field.value))
+    }
+}
+
+/// The line break of a line with the given length without and with the line break
+pub(crate) const fn line_break(len: usize, raw_len: usize) -> &'static [u8] {
+    match raw_len - len {
+        1 => b"\n",
+        _ => b"\r\n",
     }
 }
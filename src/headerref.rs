@@ -0,0 +1,359 @@
+//! A zero-copy HTTP 1/\*-header view that borrows from the parsed buffer
+
+use crate::{
+    bytetraits::AsBytes,
+    error::Error,
+    grammar,
+    header::{Header, HeaderFields, HeaderStartLine, StartLineLayout},
+    options::{Limit, ParseMode, ReadOptions},
+    parser::{LineScanner, Status},
+    rules::ListElements,
+    version::Version,
+};
+use std::borrow::Cow;
+
+/// A borrowed HTTP header
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct HeaderRef<'a> {
+    /// The start line
+    start_line: HeaderStartLineRef<'a>,
+    /// The header fields
+    fields: HeaderFieldsRef<'a>,
+    /// Whether the start line is a simple HTTP/0.9 request line or not
+    is_simple_request: bool,
+}
+impl<'a> HeaderRef<'a> {
+    /// Parses a borrowed HTTP header from `buf` using the default [`ReadOptions`]
+    ///
+    /// Returns the header together with the amount of consumed bytes, or [`Status::Partial`] if `buf` does not contain
+    /// a complete header yet.
+    pub fn parse(buf: &'a [u8]) -> Result<Status<(Self, usize)>, Error> {
+        Self::parse_with(buf, &ReadOptions::default())
+    }
+    /// Parses a borrowed HTTP header from `buf` using the given `options`
+    ///
+    /// Returns the header together with the amount of consumed bytes, or [`Status::Partial`] if `buf` does not contain
+    /// a complete header yet.
+    pub fn parse_with(buf: &'a [u8], options: &ReadOptions) -> Result<Status<(Self, usize)>, Error> {
+        // Parse the start line
+        let mut scanner = LineScanner::default();
+        let (line, offset) = match scanner.next_line(buf, options, Limit::StartLine)? {
+            Some(line) => line,
+            None => return Ok(Status::Partial),
+        };
+        let (start_line, is_simple_request) = HeaderStartLineRef::parse(line, offset, options)?;
+        if is_simple_request {
+            let fields = HeaderFieldsRef::new();
+            return Ok(Status::Complete((Self { start_line, fields, is_simple_request }, scanner.offset())));
+        }
+
+        // Parse the header fields
+        let mut fields = HeaderFieldsRef::new();
+        'parse_fields: loop {
+            let (line, offset) = match scanner.next_line(buf, options, Limit::FieldLine)? {
+                Some(line) => line,
+                None => return Ok(Status::Partial),
+            };
+            match line {
+                [] => break 'parse_fields,
+                _ if fields.len() >= options.max_field_count => {
+                    return Err(Error::LimitExceeded { limit: Limit::FieldCount, offset })
+                }
+                line => fields.parse_line(line, offset, options.mode)?,
+            }
+        }
+        Ok(Status::Complete((Self { start_line, fields, is_simple_request }, scanner.offset())))
+    }
+
+    /// The start line
+    pub fn start_line(&self) -> &HeaderStartLineRef<'a> {
+        &self.start_line
+    }
+    /// Whether the header is a request header or not
+    pub fn is_request(&self) -> bool {
+        self.start_line.is_request()
+    }
+    /// Whether the header is a response header or not
+    pub fn is_response(&self) -> bool {
+        self.start_line.is_response()
+    }
+
+    /// The header fields
+    pub fn fields(&self) -> &HeaderFieldsRef<'a> {
+        &self.fields
+    }
+
+    /// Copies the borrowed header into an owned [`Header`]
+    pub fn to_owned(&self) -> Header {
+        let layout = StartLineLayout::canonical(self.is_simple_request);
+        Header::with_layout(self.start_line.to_owned(), layout, self.fields.to_owned())
+    }
+}
+
+/// A borrowed start line
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum HeaderStartLineRef<'a> {
+    /// A request line (see RFC 9112, section 3)
+    Request {
+        /// The request method
+        method: &'a [u8],
+        /// The request target
+        target: &'a [u8],
+        /// The HTTP version
+        version: &'a [u8],
+    },
+    /// A status line (see RFC 9112, section 4)
+    Response {
+        /// The HTTP version
+        version: &'a [u8],
+        /// The status code
+        status: &'a [u8],
+        /// The reason phrase
+        reason: &'a [u8],
+    },
+}
+impl<'a> HeaderStartLineRef<'a> {
+    /// Parses the start line at `offset` from `line` (excluding the line break)
+    ///
+    /// ## Note
+    /// A start line that begins with `HTTP/` is a status line; any other start line is a request line. The reason phrase
+    /// of a status line may be empty; in lenient mode, it may also be absent together with the preceding space. If
+    /// enabled, a simple HTTP/0.9 request line is parsed as request with version `HTTP/0.9`; the returned flag tells
+    /// whether the line has been such a simple request line (i.e. without version and header fields) or not.
+    pub(crate) fn parse(line: &'a [u8], offset: usize, options: &ReadOptions) -> Result<(Self, bool), Error> {
+        let mode = options.mode;
+        // Split the start line into its fields
+        let mut fields = line.splitn(3, |b| *b == b' ');
+        let (this, is_simple_request) = match (fields.next(), fields.next(), fields.next()) {
+            (Some(version), Some(status), reason) if version.starts_with(b"HTTP/") => match reason {
+                None if mode == ParseMode::Strict => {
+                    return Err(Error::InvalidStartLine { offset: offset + line.len() })
+                }
+                reason => (Self::Response { version, status, reason: reason.unwrap_or_default() }, false),
+            },
+            (Some(method), Some(target), Some(version)) => (Self::Request { method, target, version }, false),
+            (Some(method @ b"GET"), Some(target), None) if options.allow_http09 => {
+                (Self::Request { method, target, version: Version::Http09.as_bytes() }, true)
+            }
+            _ => return Err(Error::InvalidStartLine { offset }),
+        };
+
+        // Validate the fields
+        if this.field0().is_empty() || this.field1().is_empty() || (this.is_request() && this.field2().is_empty()) {
+            return Err(Error::InvalidStartLine { offset });
+        }
+        if mode == ParseMode::Strict {
+            this.validate_strict().map_err(|pos| Error::InvalidStartLine { offset: offset + pos })?;
+        }
+        Ok((this, is_simple_request))
+    }
+    /// Validates the start line according to RFC 9112, section 3 and 4
+    fn validate_strict(&self) -> Result<(), usize> {
+        // Compute the field offsets within the line
+        let (offset1, offset2) = (self.field0().len() + 1, self.field0().len() + self.field1().len() + 2);
+        match self {
+            Self::Request { method, target, version } => {
+                grammar::validate_token(method)?;
+                grammar::validate_request_target(target).map_err(|pos| offset1 + pos)?;
+                grammar::validate_http_version(version).map_err(|pos| offset2 + pos)
+            }
+            Self::Response { version, status, reason } => {
+                grammar::validate_http_version(version)?;
+                grammar::validate_status_code(status).map_err(|pos| offset1 + pos)?;
+                grammar::validate_reason_phrase(reason).map_err(|pos| offset2 + pos)
+            }
+        }
+    }
+
+    /// Whether the start line is a request line or not
+    pub fn is_request(&self) -> bool {
+        matches!(self, Self::Request { .. })
+    }
+    /// Whether the start line is a status line or not
+    pub fn is_response(&self) -> bool {
+        matches!(self, Self::Response { .. })
+    }
+
+    /// The request method if the start line is a request line
+    pub fn method(&self) -> Option<&'a [u8]> {
+        match self {
+            Self::Request { method, .. } => Some(method),
+            Self::Response { .. } => None,
+        }
+    }
+    /// The request target if the start line is a request line
+    pub fn target(&self) -> Option<&'a [u8]> {
+        match self {
+            Self::Request { target, .. } => Some(target),
+            Self::Response { .. } => None,
+        }
+    }
+    /// The HTTP version
+    pub fn version(&self) -> &'a [u8] {
+        match self {
+            Self::Request { version, .. } | Self::Response { version, .. } => version,
+        }
+    }
+    /// The status code if the start line is a status line
+    pub fn status(&self) -> Option<&'a [u8]> {
+        match self {
+            Self::Response { status, .. } => Some(status),
+            Self::Request { .. } => None,
+        }
+    }
+    /// The reason phrase if the start line is a status line
+    pub fn reason(&self) -> Option<&'a [u8]> {
+        match self {
+            Self::Response { reason, .. } => Some(reason),
+            Self::Request { .. } => None,
+        }
+    }
+
+    /// The first field of the header line (i.e. either "method" for requests or "version" for response)
+    pub fn field0(&self) -> &'a [u8] {
+        match self {
+            Self::Request { method: field0, .. } | Self::Response { version: field0, .. } => field0,
+        }
+    }
+    /// The second field of the header line (i.e. either "target" for requests or "status" for response)
+    pub fn field1(&self) -> &'a [u8] {
+        match self {
+            Self::Request { target: field1, .. } | Self::Response { status: field1, .. } => field1,
+        }
+    }
+    /// The third field of the header line (i.e. either "version" for requests or "reason" for response)
+    pub fn field2(&self) -> &'a [u8] {
+        match self {
+            Self::Request { version: field2, .. } | Self::Response { reason: field2, .. } => field2,
+        }
+    }
+
+    /// Copies the borrowed start line into an owned [`HeaderStartLine`]
+    pub fn to_owned(&self) -> HeaderStartLine {
+        match *self {
+            Self::Request { method, target, version } => HeaderStartLine::Request {
+                method: Cow::Owned(method.to_vec()),
+                target: Cow::Owned(target.to_vec()),
+                version: Cow::Owned(version.to_vec()),
+            },
+            Self::Response { version, status, reason } => HeaderStartLine::Response {
+                version: Cow::Owned(version.to_vec()),
+                status: Cow::Owned(status.to_vec()),
+                reason: Cow::Owned(reason.to_vec()),
+            },
+        }
+    }
+}
+
+/// Some borrowed header fields
+///
+/// ## Note
+/// Like [`HeaderFields`], this type retains all occurrences of a field name in their order of occurrence and matches
+/// field names case-insensitive.
+#[derive(Debug, Default, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct HeaderFieldsRef<'a> {
+    /// The header fields as name-value pairs in their order of occurrence
+    fields: Vec<(&'a [u8], &'a [u8])>,
+}
+impl<'a> HeaderFieldsRef<'a> {
+    /// Creates a new, empty borrowed header field map
+    pub fn new() -> Self {
+        Self { fields: Vec::new() }
+    }
+
+    /// Gets the first value for the field with the given name
+    pub fn get<'b, T>(&self, name: T) -> Option<&'a [u8]>
+    where
+        T: AsBytes<'b>,
+    {
+        self.get_all(name).next()
+    }
+    /// Gets all values for the field with the given name in their order of occurrence
+    pub fn get_all<'b, T>(&self, name: T) -> impl Iterator<Item = &'a [u8]> + use<'_, 'a, 'b, T>
+    where
+        T: AsBytes<'b>,
+    {
+        let name = name.as_bytes();
+        let pairs = self.fields.iter().filter(move |(key, _)| key.eq_ignore_ascii_case(name));
+        pairs.map(|(_, value)| *value)
+    }
+    /// Gets the elements of the comma-separated list field with the given name across all of its occurrences (see
+    /// RFC 9110, section 5.6.1)
+    pub fn get_list<'b, T>(&self, name: T) -> impl Iterator<Item = &'a [u8]> + use<'_, 'a, 'b, T>
+    where
+        T: AsBytes<'b>,
+    {
+        self.get_all(name).flat_map(ListElements::new)
+    }
+
+    /// An iterator over all name-value pairs in their order of occurrence
+    pub fn iter(&self) -> impl Iterator<Item = (&'a [u8], &'a [u8])> + '_ {
+        self.fields.iter().copied()
+    }
+    /// The amount of name-value pairs
+    pub fn len(&self) -> usize {
+        self.fields.len()
+    }
+    /// Whether there are no header fields or not
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// Parses a header field line at `offset` from `line` (excluding the line break) and appends the field
+    pub(crate) fn parse_line(&mut self, line: &'a [u8], offset: usize, mode: ParseMode) -> Result<(), Error> {
+        let pair = parse_field_line(line, offset, mode)?;
+        self.fields.push(pair);
+        Ok(())
+    }
+
+    /// Copies the borrowed header fields into owned [`HeaderFields`]
+    pub fn to_owned(&self) -> HeaderFields {
+        let mut fields = HeaderFields::new();
+        for (name, value) in self.fields.iter() {
+            fields.append_raw(name.to_vec(), value.to_vec());
+        }
+        fields
+    }
+}
+
+/// Parses a header field line at `offset` from `line` (excluding the line break) into a name-value pair
+pub(crate) fn parse_field_line(line: &[u8], offset: usize, mode: ParseMode) -> Result<(&[u8], &[u8]), Error> {
+    // Reject obsolete line folding, which would otherwise be misinterpreted as a field name
+    if line.first().is_some_and(|b| grammar::is_ows(*b)) {
+        return Err(Error::InvalidFieldName { offset });
+    }
+
+    // Split the line into key-value
+    let (key, value) = match line.iter().position(|b| *b == b':') {
+        Some(0) | None => return Err(Error::InvalidFieldName { offset }),
+        Some(colon) => (&line[..colon], &line[colon + 1..]),
+    };
+
+    // Validate the key in any mode, since whitespace before the colon enables request smuggling (see RFC 9112, section
+    // 5.1)
+    grammar::validate_token(key).map_err(|pos| Error::InvalidFieldName { offset: offset + pos })?;
+
+    // Trim the surrounding whitespace
+    let value_offset = offset + (line.len() - value.len()) + value.iter().take_while(|b| grammar::is_ows(**b)).count();
+    let value = trim_ows(value);
+
+    // Validate the value
+    match mode {
+        ParseMode::Strict => {
+            grammar::validate_field_value(value)
+                .map_err(|pos| Error::InvalidFieldValue { offset: value_offset + pos })?;
+        }
+        ParseMode::Lenient if value.iter().any(|b| matches!(b, b'\r' | b'\n' | b'\0')) => {
+            return Err(Error::InvalidFieldValue { offset })
+        }
+        ParseMode::Lenient => (/* the field is tolerable */),
+    }
+    Ok((key, value))
+}
+
+/// Trims leading and trailing optional whitespace from `bytes`
+fn trim_ows(bytes: &[u8]) -> &[u8] {
+    let leading = bytes.iter().take_while(|b| grammar::is_ows(**b)).count();
+    let trailing = bytes[leading..].iter().rev().take_while(|b| grammar::is_ows(**b)).count();
+    &bytes[leading..bytes.len() - trailing]
+}
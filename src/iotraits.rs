@@ -1,17 +1,13 @@
 //! Some I/O helper traits
 
-use std::{
-    io::{BufRead, Error, ErrorKind},
-    slice,
-};
+use crate::error::Error;
+use std::io::BufRead;
 
 /// Config for pattern matching
 #[derive(Debug, PartialEq, Eq)]
 pub enum MatchConfig {
     /// Treat an EOF before a match occurrs as error
     Required,
-    /// Whether the matched pattern should be trimmed or not
-    Trim,
 }
 
 /// A convenience extension for `BufRead`
@@ -19,44 +15,16 @@ pub trait BufReadExt
 where
     Self: BufRead,
 {
-    /// Takes a peek at the next byte or returns `None` in case of EOF
-    fn peek_one(&mut self) -> Result<Option<u8>, Error>;
-
-    /// Reads the next byte or returns `None` in case of EOF
-    fn read_one(&mut self) -> Result<Option<u8>, Error>;
-
-    /// Reads a single "word" terminated by `delimiter` (not included)
+    /// Reads a single "word" terminated by `delimiter` (included)
     fn read_word<T, F>(&mut self, delimiter: T, flags: F) -> Result<Vec<u8>, Error>
     where
         T: AsRef<[u8]>,
         F: AsRef<[MatchConfig]>;
-
-    /// Reads the remaining bytes from `source`
-    fn read_all<T>(&mut self, flags: T) -> Result<Vec<u8>, Error>
-    where
-        T: AsRef<[MatchConfig]>;
 }
 impl<T> BufReadExt for T
 where
     T: BufRead,
 {
-    fn peek_one(&mut self) -> Result<Option<u8>, Error> {
-        let buf = self.fill_buf()?;
-        Ok(buf.first().copied())
-    }
-
-    fn read_one(&mut self) -> Result<Option<u8>, Error> {
-        // Check if we are EOF
-        if self.fill_buf()?.is_empty() {
-            return Ok(None);
-        }
-
-        // Read the next byte
-        let mut byte = 0;
-        self.read_exact(slice::from_mut(&mut byte))?;
-        Ok(Some(byte))
-    }
-
     fn read_word<D, F>(&mut self, delimiter: D, flags: F) -> Result<Vec<u8>, Error>
     where
         D: AsRef<[u8]>,
@@ -67,40 +35,57 @@ where
         let flags = flags.as_ref();
         let mut line = Vec::new();
 
-        // Read the word
-        'read_loop: while !line.ends_with(delimiter) {
-            match self.read_one()? {
-                Some(next) => line.push(next),
-                None => break 'read_loop,
+        // Read the word chunk by chunk
+        'read_loop: loop {
+            // Get the next chunk or stop on EOF
+            let chunk = self.fill_buf()?;
+            if chunk.is_empty() {
+                break 'read_loop;
+            }
+
+            // Scan the chunk for the end of the delimiter (note: the delimiter may span across the chunk boundary)
+            let end = match find_spanning(&line, chunk, delimiter) {
+                Some(end) => Some(end),
+                None => find(chunk, delimiter).map(|position| position + delimiter.len()),
+            };
+
+            // Consume the bytes up to the end of the delimiter only
+            let (len, is_complete) = match end {
+                Some(end) => (end, true),
+                None => (chunk.len(), false),
+            };
+            line.extend_from_slice(&chunk[..len]);
+            self.consume(len);
+            if is_complete {
+                break 'read_loop;
             }
         }
 
         // Assert that the delimiter exists if required
         if flags.contains(&MatchConfig::Required) && !line.ends_with(delimiter) {
-            return Err(ErrorKind::UnexpectedEof.into());
-        }
-
-        // Trim the match if required
-        if flags.contains(&MatchConfig::Trim) && line.ends_with(delimiter) {
-            let len_without_delimiter = line.len() - delimiter.len();
-            line.resize(len_without_delimiter, 0);
+            return Err(Error::Incomplete);
         }
         Ok(line)
     }
+}
 
-    fn read_all<F>(&mut self, flags: F) -> Result<Vec<u8>, Error>
-    where
-        F: AsRef<[MatchConfig]>,
-    {
-        // Load the flags and read all data
-        let flags = flags.as_ref();
-        let mut buf = Vec::new();
-        self.read_to_end(&mut buf)?;
-
-        // Check for early EOF
-        if flags.contains(&MatchConfig::Required) && buf.is_empty() {
-            return Err(ErrorKind::UnexpectedEof.into());
+/// Finds the first occurrence of `needle` within `haystack`
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    match needle {
+        [] => Some(0),
+        [first, rest @ ..] => {
+            // Look for the first byte and compare the remaining bytes
+            let candidates = haystack.iter().enumerate().filter(|(_, byte)| *byte == first);
+            let mut candidates = candidates.map(|(position, _)| position);
+            candidates.find(|position| haystack[position + 1..].starts_with(rest))
         }
-        Ok(buf)
     }
 }
+
+/// Finds the end of a `needle` that starts within `head` and ends within `tail`, relative to `tail`
+fn find_spanning(head: &[u8], tail: &[u8], needle: &[u8]) -> Option<usize> {
+    // Test the possible splits in order of occurrence
+    let splits = (1..needle.len()).rev();
+    let mut splits = splits.filter(|split| head.ends_with(&needle[..*split]) && tail.starts_with(&needle[*split..]));
+    splits.next().map(|split| needle.len() - split)
+}
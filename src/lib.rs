@@ -1,10 +1,44 @@
 #![doc = include_str!("../README.md")]
 
+mod authority;
+mod body;
+pub mod bytetraits;
 #[cfg(feature = "convenience")]
 pub mod convenience;
-pub mod bytetraits;
+mod date;
+mod error;
+mod grammar;
 mod header;
+mod headerref;
 mod iotraits;
+mod mediatype;
+mod message;
+mod method;
+mod negotiation;
+mod options;
+mod parser;
+pub mod rules;
+mod status;
+mod target;
+pub mod typed;
+mod version;
 
-// Re-export public header types
-pub use crate::header::{Header, HeaderFields, HeaderStartLine};
+// Re-export public types
+pub use crate::{
+    authority::{Authority, Host},
+    body::{Body, ChunkedReader, ChunkedWriter, ContentLengthReader, ContentLengthWriter, Framing},
+    date::HttpDate,
+    error::Error,
+    header::{Header, HeaderFields, HeaderStartLine},
+    headerref::{HeaderFieldsRef, HeaderRef, HeaderStartLineRef},
+    mediatype::MediaType,
+    message::Message,
+    method::Method,
+    negotiation::{Accept, AcceptCharset, AcceptEncoding, AcceptLanguage, Negotiate, QValue},
+    options::{Limit, ParseMode, ReadOptions},
+    parser::{HeaderParser, Status},
+    status::StatusCode,
+    target::RequestTarget,
+    typed::TypedHeader,
+    version::Version,
+};
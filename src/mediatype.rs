@@ -0,0 +1,136 @@
+//! Media types
+
+use crate::{
+    bytetraits::IntoBytes,
+    error::Error,
+    grammar,
+    rules::{self, Parameters},
+};
+use std::{
+    borrow::Cow,
+    fmt::{self, Display, Formatter},
+};
+
+/// A media type like `text/html; charset=utf-8` (see RFC 9110, section 8.3.1)
+///
+/// ## Note
+/// Type, subtype and parameter names are case-insensitive and stored in lowercase, so media types compare
+/// case-insensitively. Parameter values are stored unescaped and compared case-sensitively, except for the `charset`
+/// parameter which is stored in lowercase, too.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MediaType {
+    /// The lowercase `type/subtype`
+    essence: Vec<u8>,
+    /// The offset of the `/` within the essence
+    separator: usize,
+    /// The parameters as lowercase name and unescaped value in their order of occurrence
+    parameters: Vec<(Vec<u8>, Vec<u8>)>,
+}
+impl MediaType {
+    /// Creates a new media type without parameters or fails if `type_` or `subtype` is not a valid token
+    pub fn new(type_: &[u8], subtype: &[u8]) -> Result<Self, Error> {
+        grammar::validate_token(type_).map_err(|offset| Error::InvalidFieldValue { offset })?;
+        grammar::validate_token(subtype).map_err(|offset| Error::InvalidFieldValue { offset })?;
+        let essence = [type_, b"/", subtype].concat().to_ascii_lowercase();
+        Ok(Self { essence, separator: type_.len(), parameters: Vec::new() })
+    }
+    /// Adds a parameter or fails if `name` is not a valid token or `value` contains control characters
+    ///
+    /// ## Note
+    /// The value is quoted on serialization if necessary.
+    pub fn with_parameter(mut self, name: &[u8], value: &[u8]) -> Result<Self, Error> {
+        grammar::validate_token(name).map_err(|offset| Error::InvalidFieldValue { offset })?;
+        let is_valid = |byte: &u8| grammar::is_field_vchar(*byte) || grammar::is_ows(*byte);
+        if let Some(offset) = value.iter().position(|byte| !is_valid(byte)) {
+            return Err(Error::InvalidFieldValue { offset });
+        }
+        self.push_parameter(name, value.to_vec());
+        Ok(self)
+    }
+    /// Adds a parameter with normalized casing
+    fn push_parameter(&mut self, name: &[u8], mut value: Vec<u8>) {
+        // Charset names are case-insensitive (see RFC 9110, section 8.3.2)
+        let name = name.to_ascii_lowercase();
+        if name == b"charset" {
+            value.make_ascii_lowercase();
+        }
+        self.parameters.push((name, value));
+    }
+
+    /// Parses a media type from `bytes` (e.g. the value of a `Content-Type` field)
+    ///
+    /// ## Note
+    /// Parameter values may be tokens or quoted strings; empty parameters (i.e. `;;`) are ignored. The offset of the
+    /// returned [`Error::InvalidFieldValue`] is relative to `bytes`.
+    pub fn parse(bytes: &[u8]) -> Result<Self, Error> {
+        let error = |offset| Error::InvalidFieldValue { offset };
+
+        // Parse the essence
+        let end = bytes.iter().position(|byte| *byte == b';' || grammar::is_ows(*byte)).unwrap_or(bytes.len());
+        let separator = bytes[..end].iter().position(|byte| *byte == b'/').ok_or(error(end))?;
+        let (type_, subtype) = (&bytes[..separator], &bytes[separator + 1..end]);
+        grammar::validate_token(type_).map_err(error)?;
+        grammar::validate_token(subtype).map_err(|offset| error(separator + 1 + offset))?;
+        let mut this = Self::new(type_, subtype)?;
+
+        // Parse the parameters
+        for parameter in Parameters::new(&bytes[end..]) {
+            let (name, value) = parameter.map_err(|error| rules::shift_error(error, end))?;
+            this.push_parameter(name, value.into_owned());
+        }
+        Ok(this)
+    }
+
+    /// The lowercase type (e.g. `text`)
+    pub fn type_(&self) -> &[u8] {
+        &self.essence[..self.separator]
+    }
+    /// The lowercase subtype (e.g. `html`)
+    pub fn subtype(&self) -> &[u8] {
+        &self.essence[self.separator + 1..]
+    }
+    /// The lowercase `type/subtype` without parameters (e.g. `text/html`)
+    pub fn essence(&self) -> &[u8] {
+        &self.essence
+    }
+
+    /// The unescaped value of the first parameter with the given name (case-insensitive)
+    pub fn parameter(&self, name: &[u8]) -> Option<&[u8]> {
+        let mut parameters = self.parameters();
+        parameters.find(|(key, _)| key.eq_ignore_ascii_case(name)).map(|(_, value)| value)
+    }
+    /// The parameters as lowercase name and unescaped value in their order of occurrence
+    pub fn parameters(&self) -> impl Iterator<Item = (&[u8], &[u8])> {
+        self.parameters.iter().map(|(name, value)| (name.as_slice(), value.as_slice()))
+    }
+    /// The `charset` parameter if any
+    pub fn charset(&self) -> Option<&[u8]> {
+        self.parameter(b"charset")
+    }
+    /// The `boundary` parameter of `multipart` types if any (see RFC 2046, section 5.1.1)
+    pub fn boundary(&self) -> Option<&[u8]> {
+        self.parameter(b"boundary")
+    }
+
+    /// Serializes the media type with parameter values quoted as necessary
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.essence.clone();
+        for (name, value) in &self.parameters {
+            bytes.extend_from_slice(b"; ");
+            bytes.extend_from_slice(name);
+            bytes.push(b'=');
+            grammar::write_token_or_quoted_string(value, &mut bytes);
+        }
+        bytes
+    }
+}
+impl Display for MediaType {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", String::from_utf8_lossy(&self.to_bytes()))
+    }
+}
+impl IntoBytes for MediaType {
+    fn into_bytes(self) -> Cow<'static, [u8]> {
+        Cow::Owned(self.to_bytes())
+    }
+}
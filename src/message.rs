@@ -0,0 +1,121 @@
+//! A HTTP message consisting of a header and a body
+
+use crate::{
+    body::{Body, ChunkedWriter, ContentLengthWriter, Framing},
+    error::Error,
+    header::Header,
+    options::ReadOptions,
+};
+use std::io::{self, BufRead, ErrorKind, Read, Write};
+
+/// A HTTP message
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub struct Message<B> {
+    /// The header
+    header: Header,
+    /// The body
+    body: B,
+}
+impl<B> Message<B> {
+    /// Creates a new HTTP message
+    pub const fn new(header: Header, body: B) -> Self {
+        Self { header, body }
+    }
+
+    /// The header
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
+    /// The header
+    pub fn header_mut(&mut self) -> &mut Header {
+        &mut self.header
+    }
+
+    /// The body
+    pub fn body(&self) -> &B {
+        &self.body
+    }
+    /// The body
+    pub fn body_mut(&mut self) -> &mut B {
+        &mut self.body
+    }
+
+    /// Splits the message into header and body
+    pub fn into_parts(self) -> (Header, B) {
+        (self.header, self.body)
+    }
+}
+impl<R> Message<Body<R>>
+where
+    R: BufRead,
+{
+    /// Reads a HTTP message from `source` using the default [`ReadOptions`]
+    ///
+    /// ## Note
+    /// Only the header is read immediately; the body is read lazily from `source` via the returned [`Body`]. Since the
+    /// corresponding request is unknown, responses to `HEAD` and `CONNECT` requests are not detected; see
+    /// [`Message::read_from_with`] to specify the request method.
+    pub fn read_from(source: R) -> Result<Self, Error> {
+        Self::read_from_with(source, &ReadOptions::default(), None)
+    }
+    /// Reads a HTTP message from `source` using the given `options`
+    ///
+    /// For responses, `request_method` is the method of the corresponding request if known (see
+    /// [`Header::body_framing`]).
+    pub fn read_from_with(mut source: R, options: &ReadOptions, request_method: Option<&[u8]>) -> Result<Self, Error> {
+        let header = Header::read_with(&mut source, options)?;
+        let framing = header.body_framing(request_method)?;
+        Ok(Self { header, body: Body::with_options(source, framing, *options) })
+    }
+}
+impl<B> Message<B>
+where
+    B: Read,
+{
+    /// Writes the HTTP message
+    ///
+    /// ## Note
+    /// The body is framed according to the header (see [`Header::body_framing`]); i.e. it is encoded if the header
+    /// specifies `chunked` transfer coding, and it must match the `Content-Length` field if present. If the message has
+    /// no body according to its header (e.g. a request without `Content-Length` or `Transfer-Encoding`), the body must
+    /// be empty. Invalid framing and a body for a message without body are detected before anything is written; a body
+    /// that does not match the `Content-Length` is only detected after the header has been written.
+    ///
+    /// Since the corresponding request is unknown, responses to `HEAD` and `CONNECT` requests are not detected; see
+    /// [`Message::write_to_with`] to specify the request method.
+    pub fn write_to(&mut self, output: &mut dyn Write) -> Result<(), io::Error> {
+        self.write_to_with(output, None)
+    }
+    /// Writes the HTTP message like [`Message::write_to`]
+    ///
+    /// For responses, `request_method` is the method of the corresponding request if known (see
+    /// [`Header::body_framing`]); e.g. a response to a `HEAD` request is written without body, even if it has a
+    /// `Content-Length` field.
+    pub fn write_to_with(&mut self, output: &mut dyn Write, request_method: Option<&[u8]>) -> Result<(), io::Error> {
+        // Validate the framing
+        let framing = self.header.body_framing(request_method)?;
+        if framing == Framing::None && self.body.read(&mut [0])? > 0 {
+            return Err(io::Error::new(ErrorKind::InvalidInput, "body is not allowed without framing fields"));
+        }
+
+        // Write the message
+        self.header.write_all(output)?;
+        match framing {
+            Framing::None => (),
+            Framing::ContentLength(length) => {
+                let mut writer = ContentLengthWriter::new(&mut *output, length);
+                io::copy(&mut self.body, &mut writer)?;
+                writer.finish()?;
+            }
+            Framing::Chunked => {
+                let mut writer = ChunkedWriter::new(&mut *output);
+                io::copy(&mut self.body, &mut writer)?;
+                writer.finish()?;
+            }
+            Framing::UntilClose => {
+                io::copy(&mut self.body, output)?;
+            }
+        }
+        output.flush()
+    }
+}
@@ -0,0 +1,116 @@
+//! HTTP request methods
+
+use crate::{bytetraits::IntoBytes, error::Error, grammar};
+use std::{
+    borrow::Cow,
+    fmt::{self, Display, Formatter},
+};
+
+/// A HTTP request method (see RFC 9110, section 9)
+///
+/// ## Note
+/// Methods are case-sensitive; i.e. `get` is parsed as extension method. To ensure that standard methods are always
+/// represented by their dedicated variant, create methods from bytes via [`Method::parse`].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Method {
+    /// `GET` (see RFC 9110, section 9.3.1)
+    Get,
+    /// `HEAD` (see RFC 9110, section 9.3.2)
+    Head,
+    /// `POST` (see RFC 9110, section 9.3.3)
+    Post,
+    /// `PUT` (see RFC 9110, section 9.3.4)
+    Put,
+    /// `DELETE` (see RFC 9110, section 9.3.5)
+    Delete,
+    /// `CONNECT` (see RFC 9110, section 9.3.6)
+    Connect,
+    /// `OPTIONS` (see RFC 9110, section 9.3.7)
+    Options,
+    /// `TRACE` (see RFC 9110, section 9.3.8)
+    Trace,
+    /// `PATCH` (see RFC 5789)
+    Patch,
+    /// An extension method token
+    Extension(Cow<'static, [u8]>),
+}
+impl Method {
+    /// Parses a method token from `bytes`
+    ///
+    /// ## Note
+    /// The offset of the returned [`Error::InvalidStartLine`] is relative to `bytes`.
+    pub fn parse(bytes: &[u8]) -> Result<Self, Error> {
+        grammar::validate_token(bytes).map_err(|offset| Error::InvalidStartLine { offset })?;
+        Ok(match bytes {
+            b"GET" => Self::Get,
+            b"HEAD" => Self::Head,
+            b"POST" => Self::Post,
+            b"PUT" => Self::Put,
+            b"DELETE" => Self::Delete,
+            b"CONNECT" => Self::Connect,
+            b"OPTIONS" => Self::Options,
+            b"TRACE" => Self::Trace,
+            b"PATCH" => Self::Patch,
+            extension => Self::Extension(Cow::Owned(extension.to_vec())),
+        })
+    }
+
+    /// The method token
+    pub fn as_bytes(&self) -> &[u8] {
+        match self {
+            Self::Extension(extension) => extension,
+            method => method.standard_token().unwrap_or_default(),
+        }
+    }
+    /// The method token if the method is a standard method
+    const fn standard_token(&self) -> Option<&'static [u8]> {
+        match self {
+            Self::Get => Some(b"GET"),
+            Self::Head => Some(b"HEAD"),
+            Self::Post => Some(b"POST"),
+            Self::Put => Some(b"PUT"),
+            Self::Delete => Some(b"DELETE"),
+            Self::Connect => Some(b"CONNECT"),
+            Self::Options => Some(b"OPTIONS"),
+            Self::Trace => Some(b"TRACE"),
+            Self::Patch => Some(b"PATCH"),
+            Self::Extension(_) => None,
+        }
+    }
+
+    /// Whether the method is safe (i.e. read-only) or not (see RFC 9110, section 9.2.1)
+    ///
+    /// ## Note
+    /// Extension methods are never considered safe.
+    pub fn is_safe(&self) -> bool {
+        matches!(self, Self::Get | Self::Head | Self::Options | Self::Trace)
+    }
+    /// Whether the method is idempotent or not (see RFC 9110, section 9.2.2)
+    ///
+    /// ## Note
+    /// Extension methods are never considered idempotent.
+    pub fn is_idempotent(&self) -> bool {
+        self.is_safe() || matches!(self, Self::Put | Self::Delete)
+    }
+    /// Whether responses to the method are cacheable or not (see RFC 9110, section 9.2.3)
+    ///
+    /// ## Note
+    /// Responses to `POST` are only cacheable with explicit freshness information and a `Content-Location` field that
+    /// matches the target URI (see RFC 9110, section 9.3.3).
+    pub fn is_cacheable(&self) -> bool {
+        matches!(self, Self::Get | Self::Head | Self::Post)
+    }
+}
+impl Display for Method {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", String::from_utf8_lossy(self.as_bytes()))
+    }
+}
+impl IntoBytes for Method {
+    fn into_bytes(self) -> Cow<'static, [u8]> {
+        match self {
+            Self::Extension(extension) => extension,
+            method => Cow::Borrowed(method.standard_token().unwrap_or_default()),
+        }
+    }
+}
@@ -0,0 +1,344 @@
+//! Proactive content negotiation
+
+use crate::{
+    error::Error,
+    grammar,
+    mediatype::MediaType,
+    rules::{self, ListElements},
+    typed::TypedHeader,
+};
+use std::fmt::{self, Display, Formatter};
+
+/// A quality value between `0` and `1` with three decimal places (see RFC 9110, section 12.4.2)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct QValue(u16);
+impl QValue {
+    /// The quality value `0` (i.e. "not acceptable")
+    pub const ZERO: Self = Self(0);
+    /// The quality value `1` (i.e. the default)
+    pub const ONE: Self = Self(1000);
+
+    /// Creates a new quality value from thousandths or returns `None` if `millis` is greater than `1000`
+    pub const fn from_millis(millis: u16) -> Option<Self> {
+        match millis {
+            0..=1000 => Some(Self(millis)),
+            _ => None,
+        }
+    }
+    /// Parses a `qvalue` like `0.8` or `1.000`
+    ///
+    /// ## Note
+    /// The offset of the returned [`Error::InvalidFieldValue`] is relative to `bytes`.
+    pub fn parse(bytes: &[u8]) -> Result<Self, Error> {
+        let error = |offset| Error::InvalidFieldValue { offset };
+        let integer = match bytes.first() {
+            Some(b'0') => 0,
+            Some(b'1') => 1000,
+            _ => return Err(error(0)),
+        };
+
+        // Parse the up to three decimal places
+        let decimals = match bytes.get(1) {
+            None => return Ok(Self(integer)),
+            Some(b'.') => &bytes[2..],
+            Some(_) => return Err(error(1)),
+        };
+        let mut millis = integer;
+        for (index, digit) in decimals.iter().enumerate() {
+            match digit {
+                _ if index >= 3 => return Err(error(2 + index)),
+                b'0' => (),
+                b'1'..=b'9' if integer == 0 => millis += u16::from(digit - b'0') * [100, 10, 1][index],
+                _ => return Err(error(2 + index)),
+            }
+        }
+        Ok(Self(millis))
+    }
+
+    /// The quality value in thousandths
+    pub const fn as_millis(&self) -> u16 {
+        self.0
+    }
+}
+impl Display for QValue {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self.0 {
+            1000 => write!(f, "1"),
+            millis => write!(f, "{}", format!("0.{millis:03}").trim_end_matches('0').trim_end_matches('.')),
+        }
+    }
+}
+
+/// A header field that expresses preferences for proactive negotiation (see RFC 9110, section 12.1)
+pub trait Negotiate
+where
+    Self: TypedHeader,
+{
+    /// The type of the negotiated items
+    type Item: ?Sized;
+
+    /// The quality of `item` according to the most specific matching preference
+    fn quality(&self, item: &Self::Item) -> QValue;
+}
+
+/// The `Accept` field (see RFC 9110, section 12.5.1)
+///
+/// ## Note
+/// An empty field accepts all media types. Accept extensions after the weight are ignored.
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Accept(Vec<(MediaType, QValue)>);
+impl Accept {
+    /// Creates a new accept field from media ranges (e.g. `text/*`) and their weights or fails if a range has a
+    /// wildcard type with a specific subtype
+    pub fn new<I>(ranges: I) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = (MediaType, QValue)>,
+    {
+        let ranges: Vec<_> = ranges.into_iter().collect();
+        match ranges.iter().any(|(range, _)| range.type_() == b"*" && range.subtype() != b"*") {
+            true => Err(Error::InvalidFieldValue { offset: 0 }),
+            false => Ok(Self(ranges)),
+        }
+    }
+    /// The media ranges and their weights in their order of occurrence
+    pub fn ranges(&self) -> impl Iterator<Item = (&MediaType, QValue)> {
+        self.0.iter().map(|(range, quality)| (range, *quality))
+    }
+
+    /// Parses a single media range with optional weight
+    fn parse_range(element: &[u8]) -> Result<(MediaType, QValue), Error> {
+        // Split the weight and accept extensions from the media type parameters
+        let media_type = MediaType::parse(element)?;
+        let mut range = MediaType::new(media_type.type_(), media_type.subtype())?;
+        let mut parameters = media_type.parameters();
+        for (name, value) in parameters.by_ref() {
+            match name {
+                b"q" => return Ok((range, QValue::parse(value).map_err(|_| weight_error(element))?)),
+                _ => range = range.with_parameter(name, value)?,
+            }
+        }
+        Ok((range, QValue::ONE))
+    }
+}
+impl TypedHeader for Accept {
+    const NAME: &'static str = "accept";
+
+    fn decode(values: &[&[u8]]) -> Result<Self, Error> {
+        let mut ranges = Vec::new();
+        for (offset, element) in values.iter().flat_map(|value| ListElements::new(value).with_offsets()) {
+            let range = Self::parse_range(element).map_err(|error| rules::shift_error(error, offset))?;
+            ranges.push(range);
+        }
+        Self::new(ranges)
+    }
+    fn encode(&self) -> Vec<u8> {
+        let ranges = self.0.iter().map(|(range, quality)| encode_weighted(&range.to_bytes(), *quality));
+        ranges.collect::<Vec<_>>().join(b", ".as_slice())
+    }
+}
+impl Negotiate for Accept {
+    type Item = MediaType;
+
+    fn quality(&self, item: &MediaType) -> QValue {
+        // Compute the specificity of all matching ranges
+        let specificity = |range: &MediaType| match (range.type_(), range.subtype()) {
+            (b"*", b"*") => Some(0),
+            (type_, b"*") if type_ == item.type_() => Some(1),
+            _ if range.essence() != item.essence() => None,
+            _ if range.parameters().all(|(name, value)| item.parameter(name) == Some(value)) => {
+                Some(2 + range.parameters().count())
+            }
+            _ => None,
+        };
+        let matches = self.0.iter().filter_map(|(range, quality)| Some((specificity(range)?, *quality)));
+        most_specific(matches).unwrap_or(if self.0.is_empty() { QValue::ONE } else { QValue::ZERO })
+    }
+}
+
+/// The `Accept-Charset` field (see RFC 9110, section 12.5.2)
+///
+/// ## Note
+/// An empty field accepts all charsets.
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AcceptCharset(Vec<(Vec<u8>, QValue)>);
+impl AcceptCharset {
+    /// The charsets (or `*`) and their weights in their order of occurrence
+    pub fn charsets(&self) -> impl Iterator<Item = (&[u8], QValue)> {
+        self.0.iter().map(|(charset, quality)| (charset.as_slice(), *quality))
+    }
+}
+impl TypedHeader for AcceptCharset {
+    const NAME: &'static str = "accept-charset";
+
+    fn decode(values: &[&[u8]]) -> Result<Self, Error> {
+        decode_weighted_tokens(values, grammar::validate_token).map(Self)
+    }
+    fn encode(&self) -> Vec<u8> {
+        encode_weighted_tokens(&self.0)
+    }
+}
+impl Negotiate for AcceptCharset {
+    type Item = [u8];
+
+    fn quality(&self, item: &[u8]) -> QValue {
+        let matches = self.charsets().filter_map(|(charset, quality)| match charset {
+            b"*" => Some((0, quality)),
+            charset if charset.eq_ignore_ascii_case(item) => Some((1, quality)),
+            _ => None,
+        });
+        most_specific(matches).unwrap_or(if self.0.is_empty() { QValue::ONE } else { QValue::ZERO })
+    }
+}
+
+/// The `Accept-Encoding` field (see RFC 9110, section 12.5.3)
+///
+/// ## Note
+/// The `identity` coding is always acceptable unless it is explicitly excluded with a weight of `0`; an empty field
+/// thus only accepts `identity`.
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AcceptEncoding(Vec<(Vec<u8>, QValue)>);
+impl AcceptEncoding {
+    /// The content codings (or `*`) and their weights in their order of occurrence
+    pub fn codings(&self) -> impl Iterator<Item = (&[u8], QValue)> {
+        self.0.iter().map(|(coding, quality)| (coding.as_slice(), *quality))
+    }
+}
+impl TypedHeader for AcceptEncoding {
+    const NAME: &'static str = "accept-encoding";
+
+    fn decode(values: &[&[u8]]) -> Result<Self, Error> {
+        decode_weighted_tokens(values, grammar::validate_token).map(Self)
+    }
+    fn encode(&self) -> Vec<u8> {
+        encode_weighted_tokens(&self.0)
+    }
+}
+impl Negotiate for AcceptEncoding {
+    type Item = [u8];
+
+    fn quality(&self, item: &[u8]) -> QValue {
+        let matches = self.codings().filter_map(|(coding, quality)| match coding {
+            b"*" => Some((0, quality)),
+            coding if coding.eq_ignore_ascii_case(item) => Some((1, quality)),
+            _ => None,
+        });
+        match most_specific(matches) {
+            Some(quality) => quality,
+            None if item.eq_ignore_ascii_case(b"identity") => QValue::ONE,
+            None => QValue::ZERO,
+        }
+    }
+}
+
+/// The `Accept-Language` field with basic filtering of language ranges (see RFC 9110, section 12.5.4 and RFC 4647,
+/// section 3.3.1)
+///
+/// ## Note
+/// An empty field accepts all languages.
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AcceptLanguage(Vec<(Vec<u8>, QValue)>);
+impl AcceptLanguage {
+    /// The language ranges (or `*`) and their weights in their order of occurrence
+    pub fn ranges(&self) -> impl Iterator<Item = (&[u8], QValue)> {
+        self.0.iter().map(|(range, quality)| (range.as_slice(), *quality))
+    }
+
+    /// Validates that `bytes` is a `language-range` (see RFC 4647, section 2.1)
+    fn validate_range(bytes: &[u8]) -> Result<(), usize> {
+        if bytes == b"*" {
+            return Ok(());
+        }
+
+        // Validate the subtags
+        let mut offset = 0;
+        for (index, subtag) in bytes.split(|byte| *byte == b'-').enumerate() {
+            let is_valid = |byte: &u8| byte.is_ascii_alphabetic() || (index > 0 && byte.is_ascii_digit());
+            match subtag.iter().position(|byte| !is_valid(byte)) {
+                _ if subtag.is_empty() || subtag.len() > 8 => return Err(offset),
+                Some(position) => return Err(offset + position),
+                None => offset += subtag.len() + 1,
+            }
+        }
+        Ok(())
+    }
+}
+impl TypedHeader for AcceptLanguage {
+    const NAME: &'static str = "accept-language";
+
+    fn decode(values: &[&[u8]]) -> Result<Self, Error> {
+        decode_weighted_tokens(values, Self::validate_range).map(Self)
+    }
+    fn encode(&self) -> Vec<u8> {
+        encode_weighted_tokens(&self.0)
+    }
+}
+impl Negotiate for AcceptLanguage {
+    type Item = [u8];
+
+    fn quality(&self, item: &[u8]) -> QValue {
+        let matches = self.ranges().filter_map(|(range, quality)| {
+            let is_prefix = item.get(range.len()) == Some(&b'-') && item[..range.len()].eq_ignore_ascii_case(range);
+            match range {
+                b"*" => Some((0, quality)),
+                range if range.eq_ignore_ascii_case(item) || is_prefix => Some((range.len(), quality)),
+                _ => None,
+            }
+        });
+        most_specific(matches).unwrap_or(if self.0.is_empty() { QValue::ONE } else { QValue::ZERO })
+    }
+}
+
+/// Decodes a list of tokens with optional weights
+fn decode_weighted_tokens(
+    values: &[&[u8]],
+    validate: fn(&[u8]) -> Result<(), usize>,
+) -> Result<Vec<(Vec<u8>, QValue)>, Error> {
+    let mut items = Vec::new();
+    for (offset, element) in values.iter().flat_map(|value| ListElements::new(value).with_offsets()) {
+        // Split and validate the item
+        let mut parts = element.split(|byte| *byte == b';');
+        let item = parts.next().unwrap_or_default().trim_ascii_end();
+        validate(item).map_err(|position| Error::InvalidFieldValue { offset: offset + position })?;
+
+        // Parse the weight
+        let quality = match parts.next().map(<[u8]>::trim_ascii) {
+            None => QValue::ONE,
+            Some([b'q' | b'Q', b'=', quality @ ..]) if parts.next().is_none() => {
+                QValue::parse(quality).map_err(|_| rules::shift_error(weight_error(element), offset))?
+            }
+            Some(_) => return Err(rules::shift_error(weight_error(element), offset)),
+        };
+        items.push((item.to_vec(), quality));
+    }
+    Ok(items)
+}
+/// Encodes a list of tokens with weights
+fn encode_weighted_tokens(items: &[(Vec<u8>, QValue)]) -> Vec<u8> {
+    let items = items.iter().map(|(item, quality)| encode_weighted(item, *quality));
+    items.collect::<Vec<_>>().join(b", ".as_slice())
+}
+/// Encodes an item with its weight, omitting the default weight
+fn encode_weighted(item: &[u8], quality: QValue) -> Vec<u8> {
+    match quality {
+        QValue::ONE => item.to_vec(),
+        quality => [item, format!(";q={quality}").as_bytes()].concat(),
+    }
+}
+/// Selects the quality of the most specific match, preferring the first of equally specific matches
+fn most_specific<I>(matches: I) -> Option<QValue>
+where
+    I: IntoIterator<Item = (usize, QValue)>,
+{
+    let mut best: Option<(usize, QValue)> = None;
+    for (specificity, quality) in matches {
+        if best.is_none_or(|(best_specificity, _)| specificity > best_specificity) {
+            best = Some((specificity, quality));
+        }
+    }
+    best.map(|(_, quality)| quality)
+}
+/// The error for an invalid weight within `element`, pointing at the first `;`
+fn weight_error(element: &[u8]) -> Error {
+    let offset = element.iter().position(|byte| *byte == b';').unwrap_or_default();
+    Error::InvalidFieldValue { offset }
+}
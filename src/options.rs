@@ -0,0 +1,113 @@
+//! Options to configure header parsing
+
+use std::fmt::{self, Display, Formatter};
+
+/// A parser limit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Limit {
+    /// The start line is too long (usually answered with `414 URI Too Long`)
+    StartLine,
+    /// A header field line is too long (usually answered with `431 Request Header Fields Too Large`)
+    FieldLine,
+    /// There are too many header fields (usually answered with `431 Request Header Fields Too Large`)
+    FieldCount,
+    /// The entire header is too large (usually answered with `431 Request Header Fields Too Large`)
+    HeaderSize,
+}
+impl Display for Limit {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::StartLine => write!(f, "HTTP start line exceeds limit"),
+            Self::FieldLine => write!(f, "HTTP header field exceeds limit"),
+            Self::FieldCount => write!(f, "HTTP header field count exceeds limit"),
+            Self::HeaderSize => write!(f, "HTTP header size exceeds limit"),
+        }
+    }
+}
+
+/// The header parsing mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ParseMode {
+    /// Strict RFC 9112 conformance
+    ///
+    /// Rejects bare `\n` line breaks, obsolete line folding, invalid token characters in methods, invalid characters in
+    /// field values, versions other than `HTTP/x.y`, and status codes that are not exactly three digits.
+    Strict,
+    /// Tolerant parsing for interoperability with non-conforming peers
+    ///
+    /// Accepts bare `\n` line breaks and tolerates unusual characters in field values. Obsolete line folding is rejected
+    /// nevertheless, since it cannot be unfolded without copying.
+    ///
+    /// ## Note
+    /// Field names are validated as tokens in both modes; in particular, whitespace between a field name and the colon is
+    /// always rejected, since it is a common vector for request smuggling (see RFC 9112, section 5.1).
+    Lenient,
+}
+
+/// Options to configure header parsing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ReadOptions {
+    /// The maximum length of the start line in bytes (excluding the line break)
+    pub max_start_line: usize,
+    /// The maximum length of a single header field line in bytes (excluding the line break)
+    pub max_field_line: usize,
+    /// The maximum amount of header fields
+    pub max_field_count: usize,
+    /// The maximum total size of the header in bytes (including all line breaks and the final empty line)
+    pub max_header_size: usize,
+    /// The parsing mode
+    pub mode: ParseMode,
+    /// Whether simple HTTP/0.9 request lines (i.e. `GET /path` without version and header fields) are recognized
+    pub allow_http09: bool,
+}
+impl ReadOptions {
+    /// Creates new options with sensible default limits and lenient parsing
+    pub const fn new() -> Self {
+        Self {
+            max_start_line: 8 * 1024,
+            max_field_line: 8 * 1024,
+            max_field_count: 128,
+            max_header_size: 64 * 1024,
+            mode: ParseMode::Lenient,
+            allow_http09: false,
+        }
+    }
+    /// Creates new options without any limits and lenient parsing
+    ///
+    /// # Warning:
+    /// Reading a header without limits will read forever until there is either a `\r\n\r\n` or an EOF. To prevent
+    /// DOS-attacks, it is strongly recommended to wrap the source into a [`std::io::Take`] in this case.
+    pub const fn unlimited() -> Self {
+        Self {
+            max_start_line: usize::MAX,
+            max_field_line: usize::MAX,
+            max_field_count: usize::MAX,
+            max_header_size: usize::MAX,
+            mode: ParseMode::Lenient,
+            allow_http09: false,
+        }
+    }
+
+    /// Gets the maximum line length excluding the line break for the line type `limit`
+    pub(crate) const fn max_line(&self, limit: Limit) -> usize {
+        match limit {
+            Limit::StartLine => self.max_start_line,
+            _ => self.max_field_line,
+        }
+    }
+    /// Gets the effective maximum length of the next line including the line break and the associated limit, given the
+    /// line type `limit` and the line's `offset` within the header
+    pub(crate) fn line_limit(&self, limit: Limit, offset: usize) -> (usize, Limit) {
+        // Select the more restrictive limit
+        let budget = self.max_header_size.saturating_sub(offset);
+        match self.max_line(limit).saturating_add(2) {
+            max_len if max_len > budget => (budget, Limit::HeaderSize),
+            max_len => (max_len, limit),
+        }
+    }
+}
+impl Default for ReadOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
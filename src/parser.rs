@@ -0,0 +1,177 @@
+//! An incremental, I/O-free header parser
+
+use crate::{
+    error::Error,
+    header::{self, Header, HeaderFields, HeaderStartLine, StartLineLayout},
+    options::{Limit, ParseMode, ReadOptions},
+};
+use std::mem;
+
+/// The result of an incremental parsing attempt
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Status<T> {
+    /// The input has been parsed completely
+    Complete(T),
+    /// The input is incomplete and more data is required
+    Partial,
+}
+impl<T> Status<T> {
+    /// Whether the status is complete or not
+    pub const fn is_complete(&self) -> bool {
+        matches!(self, Self::Complete(_))
+    }
+
+    /// Gets the completed value if any
+    pub fn complete(self) -> Option<T> {
+        match self {
+            Self::Complete(value) => Some(value),
+            Self::Partial => None,
+        }
+    }
+}
+
+/// An incremental HTTP header parser that operates on byte slices
+///
+/// ## Note
+/// The parser remembers the already parsed lines, so each call to [`HeaderParser::parse`] only processes the newly
+/// received bytes. This means that subsequent calls must pass the same buffer, with the new data appended to the end.
+#[derive(Debug, Clone)]
+pub struct HeaderParser {
+    /// The parser options
+    options: ReadOptions,
+    /// The line scanner
+    scanner: LineScanner,
+    /// The start line together with its layout if it has already been parsed
+    start_line: Option<(HeaderStartLine, StartLineLayout)>,
+    /// The header fields that have already been parsed
+    fields: HeaderFields,
+}
+impl HeaderParser {
+    /// Creates a new parser using the default [`ReadOptions`]
+    pub fn new() -> Self {
+        Self::with_options(ReadOptions::default())
+    }
+    /// Creates a new parser using the given `options`
+    pub fn with_options(options: ReadOptions) -> Self {
+        Self { options, scanner: LineScanner::default(), start_line: None, fields: HeaderFields::new() }
+    }
+
+    /// Parses the header from `buf` and returns the header together with the amount of consumed bytes, or
+    /// [`Status::Partial`] if more data is required
+    ///
+    /// ## Note
+    /// Once a header has been returned, the parser is reset and can be reused to parse the next header from a new
+    /// buffer (i.e. a buffer that starts with the next header).
+    pub fn parse(&mut self, buf: &[u8]) -> Result<Status<(Header, usize)>, Error> {
+        'parse_lines: loop {
+            // Get the next line
+            let limit = match self.start_line {
+                None => Limit::StartLine,
+                Some(_) => Limit::FieldLine,
+            };
+            let (line, offset) = match self.scanner.next_line(buf, &self.options, limit)? {
+                Some(line) => line,
+                None => return Ok(Status::Partial),
+            };
+
+            // Parse the line
+            let line_break = header::line_break(line.len(), self.scanner.offset() - offset);
+            match (&self.start_line, line) {
+                (None, line) => {
+                    let (start_line, is_simple_request) = HeaderStartLine::parse(line, offset, &self.options)?;
+                    let layout = StartLineLayout::new(line, line_break, is_simple_request);
+                    self.start_line = Some((start_line, layout));
+                    if is_simple_request {
+                        break 'parse_lines;
+                    }
+                }
+                (Some(_), []) => {
+                    self.fields.set_end(line_break);
+                    break 'parse_lines;
+                }
+                (Some(_), _) if self.fields.len() >= self.options.max_field_count => {
+                    return Err(Error::LimitExceeded { limit: Limit::FieldCount, offset })
+                }
+                (Some(_), line) => self.fields.parse_line(line, line_break, offset, self.options.mode)?,
+            }
+        }
+
+        // Assemble the header and reset the parser
+        let this = mem::replace(self, Self::with_options(self.options));
+        let (start_line, layout) = this.start_line.expect("missing start line");
+        Ok(Status::Complete((Header::with_layout(start_line, layout, this.fields), this.scanner.offset())))
+    }
+}
+impl Default for HeaderParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A line scanner that keeps track of the current position within a header
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct LineScanner {
+    /// The offset of the next line
+    offset: usize,
+    /// The amount of bytes of the next line that have already been scanned for a line break
+    scanned: usize,
+}
+impl LineScanner {
+    /// The offset of the next line
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Gets the next complete line (excluding the line break) of type `limit` from `buf` together with its offset
+    pub fn next_line<'a>(
+        &mut self,
+        buf: &'a [u8],
+        options: &ReadOptions,
+        limit: Limit,
+    ) -> Result<Option<(&'a [u8], usize)>, Error> {
+        // Get the limit for the next line
+        let (max_len, window_limit) = options.line_limit(limit, self.offset);
+
+        // Scan the unscanned bytes for a line break
+        let line = buf.get(self.offset..).unwrap_or_default();
+        let line = &line[..line.len().min(max_len)];
+        let unscanned = line.get(self.scanned..).unwrap_or_default();
+        match unscanned.iter().position(|byte| *byte == b'\n') {
+            Some(position) => {
+                let (raw, offset) = (&line[..self.scanned + position + 1], self.offset);
+                self.offset += raw.len();
+                self.scanned = 0;
+                Ok(Some((strip_line_break(raw, options, limit, offset)?, offset)))
+            }
+            None if line.len() == max_len => Err(Error::LimitExceeded { limit: window_limit, offset: self.offset }),
+            None => {
+                self.scanned = line.len();
+                Ok(None)
+            }
+        }
+    }
+}
+
+/// Strips the line break from the `\n`-terminated `line` of type `limit` at `offset` according to the parsing mode and
+/// validates the line length
+pub(crate) fn strip_line_break<'a>(
+    line: &'a [u8],
+    options: &ReadOptions,
+    limit: Limit,
+    offset: usize,
+) -> Result<&'a [u8], Error> {
+    // Strip the line break
+    let line = line.strip_suffix(b"\n").expect("missing line break");
+    let line = match (line.strip_suffix(b"\r"), options.mode) {
+        (Some(line), _) => line,
+        (None, ParseMode::Lenient) => line,
+        (None, ParseMode::Strict) if limit == Limit::StartLine => return Err(Error::InvalidStartLine { offset }),
+        (None, ParseMode::Strict) => return Err(Error::InvalidFieldValue { offset }),
+    };
+
+    // Validate the line length (note: this is necessary, since a bare `\n` is one byte shorter than `\r\n`)
+    if line.len() > options.max_line(limit) {
+        return Err(Error::LimitExceeded { limit, offset });
+    }
+    Ok(line)
+}
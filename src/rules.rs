@@ -0,0 +1,225 @@
+//! Generic field value rules (see RFC 9110, section 5.6)
+//!
+//! Helpers to parse the common building blocks of field values: comma-separated lists, tokens, quoted strings, comments
+//! and parameters. All parse errors are [`Error::InvalidFieldValue`] with an offset relative to the parsed bytes.
+
+use crate::{error::Error, grammar};
+use std::borrow::Cow;
+
+/// A parameter as name and unescaped value
+pub type Parameter<'a> = (&'a [u8], Cow<'a, [u8]>);
+/// A directive as name and optional unescaped value
+pub type Directive<'a> = (&'a [u8], Option<Cow<'a, [u8]>>);
+
+/// An iterator over the elements of a comma-separated list (see RFC 9110, section 5.6.1)
+///
+/// ## Note
+/// Elements are stripped of surrounding whitespace and empty elements are skipped. Commas within quoted strings and
+/// comments are not treated as delimiters; an unterminated quoted string or comment extends to the end of the value.
+#[derive(Debug, Clone)]
+pub struct ListElements<'a> {
+    /// The list
+    bytes: &'a [u8],
+    /// The offset of the next element
+    offset: usize,
+}
+impl<'a> ListElements<'a> {
+    /// Creates a new iterator over the elements of the list in `bytes`
+    pub const fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, offset: 0 }
+    }
+
+    /// Yields the elements together with their offsets within the list
+    pub fn with_offsets(mut self) -> impl Iterator<Item = (usize, &'a [u8])> {
+        std::iter::from_fn(move || self.next_with_offset())
+    }
+    /// Parses the next non-empty element
+    fn next_with_offset(&mut self) -> Option<(usize, &'a [u8])> {
+        while self.offset <= self.bytes.len() {
+            // Find the next delimiter outside of quoted strings and comments
+            let (start, mut end) = (self.offset, self.offset);
+            let (mut in_quotes, mut depth, mut is_escaped) = (false, 0usize, false);
+            while let Some(byte) = self.bytes.get(end) {
+                match byte {
+                    _ if is_escaped => is_escaped = false,
+                    b'\\' if in_quotes || depth > 0 => is_escaped = true,
+                    b'"' if depth == 0 => in_quotes = !in_quotes,
+                    b'(' if !in_quotes => depth += 1,
+                    b')' if !in_quotes => depth = depth.saturating_sub(1),
+                    b',' if !in_quotes && depth == 0 => break,
+                    _ => (),
+                }
+                end += 1;
+            }
+            self.offset = end + 1;
+
+            // Trim the element
+            let element = &self.bytes[start..end];
+            let leading = element.iter().position(|byte| !grammar::is_ows(*byte)).unwrap_or(element.len());
+            let trailing = element.iter().rev().position(|byte| !grammar::is_ows(*byte)).unwrap_or(0);
+            if leading < element.len() {
+                return Some((start + leading, &element[leading..element.len() - trailing]));
+            }
+        }
+        None
+    }
+}
+impl<'a> Iterator for ListElements<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_with_offset().map(|(_, element)| element)
+    }
+}
+
+/// An iterator over the parameters of a field value like `; charset=utf-8; q=1` (see RFC 9110, section 5.6.6)
+///
+/// ## Note
+/// Each parameter is yielded as name and unescaped value; empty parameters (i.e. `;;`) are skipped. The iterator ends
+/// after the first error.
+#[derive(Debug, Clone)]
+pub struct Parameters<'a> {
+    /// The parameters
+    bytes: &'a [u8],
+    /// The offset of the next parameter or `None` after the end or an error
+    offset: Option<usize>,
+}
+impl<'a> Parameters<'a> {
+    /// Creates a new iterator over the parameters in `bytes`, which must start with `;` or whitespace if not empty
+    pub const fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, offset: Some(0) }
+    }
+
+    /// Parses the parameter at `offset` and returns it together with the offset after it
+    fn parse_at(&self, mut offset: usize) -> Result<Option<(Parameter<'a>, usize)>, Error> {
+        let error = |offset| Error::InvalidFieldValue { offset };
+        loop {
+            // Skip the delimiter and empty parameters
+            offset += skip_ows(&self.bytes[offset..]);
+            match self.bytes.get(offset) {
+                None => return Ok(None),
+                Some(b';') => offset += 1,
+                Some(_) => return Err(error(offset)),
+            }
+            offset += skip_ows(&self.bytes[offset..]);
+            if !matches!(self.bytes.get(offset), None | Some(b';')) {
+                break;
+            }
+        }
+
+        // Parse the name and value
+        let name = &self.bytes[offset..offset + token_len(&self.bytes[offset..])];
+        match self.bytes.get(offset + name.len()) {
+            Some(b'=') if !name.is_empty() => offset += name.len() + 1,
+            _ => return Err(error(offset + name.len())),
+        }
+        let (value, len) = parse_token_or_quoted_string(&self.bytes[offset..]).map_err(|e| shift_error(e, offset))?;
+        Ok(Some(((name, value), offset + len)))
+    }
+}
+impl<'a> Iterator for Parameters<'a> {
+    type Item = Result<Parameter<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let result = self.parse_at(self.offset?);
+        match result {
+            Ok(Some((parameter, offset))) => {
+                self.offset = Some(offset);
+                Some(Ok(parameter))
+            }
+            Ok(None) => {
+                self.offset = None;
+                None
+            }
+            Err(error) => {
+                self.offset = None;
+                Some(Err(error))
+            }
+        }
+    }
+}
+
+/// Parses the `token` at the start of `bytes` (see RFC 9110, section 5.6.2)
+pub fn parse_token(bytes: &[u8]) -> Result<&[u8], Error> {
+    match token_len(bytes) {
+        0 => Err(Error::InvalidFieldValue { offset: 0 }),
+        len => Ok(&bytes[..len]),
+    }
+}
+/// Parses the `quoted-string` at the start of `bytes` and returns the unescaped content together with the number of
+/// consumed bytes (see RFC 9110, section 5.6.4)
+pub fn parse_quoted_string(bytes: &[u8]) -> Result<(Vec<u8>, usize), Error> {
+    grammar::parse_quoted_string(bytes).map_err(|offset| Error::InvalidFieldValue { offset })
+}
+/// Parses the `token` or `quoted-string` at the start of `bytes` and returns the unescaped value together with the
+/// number of consumed bytes
+pub fn parse_token_or_quoted_string(bytes: &[u8]) -> Result<(Cow<'_, [u8]>, usize), Error> {
+    match bytes.first() {
+        Some(b'"') => parse_quoted_string(bytes).map(|(value, len)| (Cow::Owned(value), len)),
+        _ => parse_token(bytes).map(|token| (Cow::Borrowed(token), token.len())),
+    }
+}
+/// Parses the `comment` at the start of `bytes` and returns its content without the enclosing parentheses (see RFC
+/// 9110, section 5.6.5)
+///
+/// ## Note
+/// The content is returned verbatim, i.e. nested comments and quoted pairs are not unescaped; the number of consumed
+/// bytes is the length of the content plus two.
+pub fn parse_comment(bytes: &[u8]) -> Result<&[u8], Error> {
+    let error = |offset| Error::InvalidFieldValue { offset };
+    if bytes.first() != Some(&b'(') {
+        return Err(error(0));
+    }
+
+    // Scan until the matching closing parenthesis
+    let (mut offset, mut depth) = (1, 1);
+    while let Some(byte) = bytes.get(offset) {
+        match byte {
+            b'(' => depth += 1,
+            b')' if depth == 1 => return Ok(&bytes[1..offset]),
+            b')' => depth -= 1,
+            b'\\' => match bytes.get(offset + 1) {
+                Some(escaped) if grammar::is_field_vchar(*escaped) || grammar::is_ows(*escaped) => offset += 1,
+                _ => return Err(error(offset + 1)),
+            },
+            byte if grammar::is_ctext(*byte) => (),
+            _ => return Err(error(offset)),
+        }
+        offset += 1;
+    }
+    Err(error(offset))
+}
+/// Parses a list element of the form `token [ "=" ( token / quoted-string ) ]` as used by e.g. `Cache-Control` (see
+/// RFC 9111, section 5.2)
+pub fn parse_directive(element: &[u8]) -> Result<Directive<'_>, Error> {
+    let name = parse_token(element)?;
+    let value = match element.get(name.len()) {
+        None => return Ok((name, None)),
+        Some(b'=') => &element[name.len() + 1..],
+        Some(_) => return Err(Error::InvalidFieldValue { offset: name.len() }),
+    };
+
+    // Parse the value and ensure that it spans the rest of the element
+    let offset = name.len() + 1;
+    let (value, len) = parse_token_or_quoted_string(value).map_err(|error| shift_error(error, offset))?;
+    match offset + len {
+        end if end == element.len() => Ok((name, Some(value))),
+        end => Err(Error::InvalidFieldValue { offset: end }),
+    }
+}
+
+/// The number of leading `tchar` bytes
+fn token_len(bytes: &[u8]) -> usize {
+    bytes.iter().position(|byte| !grammar::is_tchar(*byte)).unwrap_or(bytes.len())
+}
+/// The number of leading optional whitespace bytes
+fn skip_ows(bytes: &[u8]) -> usize {
+    bytes.iter().position(|byte| !grammar::is_ows(*byte)).unwrap_or(bytes.len())
+}
+/// Shifts the offset of an [`Error::InvalidFieldValue`] by `offset`
+pub(crate) fn shift_error(error: Error, offset: usize) -> Error {
+    match error {
+        Error::InvalidFieldValue { offset: position } => Error::InvalidFieldValue { offset: offset + position },
+        error => error,
+    }
+}
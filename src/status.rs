@@ -0,0 +1,220 @@
+//! HTTP status codes
+
+use crate::{bytetraits::IntoBytes, error::Error, grammar};
+use std::{
+    borrow::Cow,
+    fmt::{self, Display, Formatter},
+};
+
+/// Defines the registered status code constants together with their canonical reason phrases
+macro_rules! status_codes {
+    ($($(#[$doc:meta])* ($code:literal, $name:ident, $reason:literal);)*) => {
+        impl StatusCode {
+            $(
+                $(#[$doc])*
+                pub const $name: Self = Self($code);
+            )*
+
+            /// The canonical reason phrase for registered status codes
+            pub const fn canonical_reason(&self) -> Option<&'static str> {
+                match self.0 {
+                    $($code => Some($reason),)*
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+/// A HTTP status code (see RFC 9110, section 15)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StatusCode(u16);
+impl StatusCode {
+    /// Creates a new status code if `code` is within the valid range `100..=599`
+    pub const fn new(code: u16) -> Option<Self> {
+        match code {
+            100..=599 => Some(Self(code)),
+            _ => None,
+        }
+    }
+    /// Parses a three-digit status code from `bytes`
+    ///
+    /// ## Note
+    /// The offset of the returned [`Error::InvalidStartLine`] is relative to `bytes`.
+    pub fn parse(bytes: &[u8]) -> Result<Self, Error> {
+        grammar::validate_status_code(bytes).map_err(|offset| Error::InvalidStartLine { offset })?;
+        let code = bytes.iter().fold(0, |code, digit| code * 10 + u16::from(digit - b'0'));
+        Self::new(code).ok_or(Error::InvalidStartLine { offset: 0 })
+    }
+
+    /// The numeric status code
+    pub const fn as_u16(&self) -> u16 {
+        self.0
+    }
+
+    /// Whether the status code is informational (`1xx`) or not
+    pub const fn is_informational(&self) -> bool {
+        matches!(self.0, 100..=199)
+    }
+    /// Whether the status code indicates success (`2xx`) or not
+    pub const fn is_success(&self) -> bool {
+        matches!(self.0, 200..=299)
+    }
+    /// Whether the status code indicates a redirection (`3xx`) or not
+    pub const fn is_redirection(&self) -> bool {
+        matches!(self.0, 300..=399)
+    }
+    /// Whether the status code indicates a client error (`4xx`) or not
+    pub const fn is_client_error(&self) -> bool {
+        matches!(self.0, 400..=499)
+    }
+    /// Whether the status code indicates a server error (`5xx`) or not
+    pub const fn is_server_error(&self) -> bool {
+        matches!(self.0, 500..=599)
+    }
+}
+impl Display for StatusCode {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+impl From<StatusCode> for u16 {
+    fn from(status: StatusCode) -> Self {
+        status.0
+    }
+}
+impl IntoBytes for StatusCode {
+    fn into_bytes(self) -> Cow<'static, [u8]> {
+        Cow::Owned(self.to_string().into_bytes())
+    }
+}
+
+// The HTTP status code registry (see https://www.iana.org/assignments/http-status-codes)
+status_codes! {
+    /// `100 Continue` (see RFC 9110, section 15.2.1)
+    (100, CONTINUE, "Continue");
+    /// `101 Switching Protocols` (see RFC 9110, section 15.2.2)
+    (101, SWITCHING_PROTOCOLS, "Switching Protocols");
+    /// `102 Processing` (see RFC 2518, section 10.1)
+    (102, PROCESSING, "Processing");
+    /// `103 Early Hints` (see RFC 8297)
+    (103, EARLY_HINTS, "Early Hints");
+
+    /// `200 OK` (see RFC 9110, section 15.3.1)
+    (200, OK, "OK");
+    /// `201 Created` (see RFC 9110, section 15.3.2)
+    (201, CREATED, "Created");
+    /// `202 Accepted` (see RFC 9110, section 15.3.3)
+    (202, ACCEPTED, "Accepted");
+    /// `203 Non-Authoritative Information` (see RFC 9110, section 15.3.4)
+    (203, NON_AUTHORITATIVE_INFORMATION, "Non-Authoritative Information");
+    /// `204 No Content` (see RFC 9110, section 15.3.5)
+    (204, NO_CONTENT, "No Content");
+    /// `205 Reset Content` (see RFC 9110, section 15.3.6)
+    (205, RESET_CONTENT, "Reset Content");
+    /// `206 Partial Content` (see RFC 9110, section 15.3.7)
+    (206, PARTIAL_CONTENT, "Partial Content");
+    /// `207 Multi-Status` (see RFC 4918, section 11.1)
+    (207, MULTI_STATUS, "Multi-Status");
+    /// `208 Already Reported` (see RFC 5842, section 7.1)
+    (208, ALREADY_REPORTED, "Already Reported");
+    /// `226 IM Used` (see RFC 3229, section 10.4.1)
+    (226, IM_USED, "IM Used");
+
+    /// `300 Multiple Choices` (see RFC 9110, section 15.4.1)
+    (300, MULTIPLE_CHOICES, "Multiple Choices");
+    /// `301 Moved Permanently` (see RFC 9110, section 15.4.2)
+    (301, MOVED_PERMANENTLY, "Moved Permanently");
+    /// `302 Found` (see RFC 9110, section 15.4.3)
+    (302, FOUND, "Found");
+    /// `303 See Other` (see RFC 9110, section 15.4.4)
+    (303, SEE_OTHER, "See Other");
+    /// `304 Not Modified` (see RFC 9110, section 15.4.5)
+    (304, NOT_MODIFIED, "Not Modified");
+    /// `305 Use Proxy` (see RFC 9110, section 15.4.6)
+    (305, USE_PROXY, "Use Proxy");
+    /// `307 Temporary Redirect` (see RFC 9110, section 15.4.8)
+    (307, TEMPORARY_REDIRECT, "Temporary Redirect");
+    /// `308 Permanent Redirect` (see RFC 9110, section 15.4.9)
+    (308, PERMANENT_REDIRECT, "Permanent Redirect");
+
+    /// `400 Bad Request` (see RFC 9110, section 15.5.1)
+    (400, BAD_REQUEST, "Bad Request");
+    /// `401 Unauthorized` (see RFC 9110, section 15.5.2)
+    (401, UNAUTHORIZED, "Unauthorized");
+    /// `402 Payment Required` (see RFC 9110, section 15.5.3)
+    (402, PAYMENT_REQUIRED, "Payment Required");
+    /// `403 Forbidden` (see RFC 9110, section 15.5.4)
+    (403, FORBIDDEN, "Forbidden");
+    /// `404 Not Found` (see RFC 9110, section 15.5.5)
+    (404, NOT_FOUND, "Not Found");
+    /// `405 Method Not Allowed` (see RFC 9110, section 15.5.6)
+    (405, METHOD_NOT_ALLOWED, "Method Not Allowed");
+    /// `406 Not Acceptable` (see RFC 9110, section 15.5.7)
+    (406, NOT_ACCEPTABLE, "Not Acceptable");
+    /// `407 Proxy Authentication Required` (see RFC 9110, section 15.5.8)
+    (407, PROXY_AUTHENTICATION_REQUIRED, "Proxy Authentication Required");
+    /// `408 Request Timeout` (see RFC 9110, section 15.5.9)
+    (408, REQUEST_TIMEOUT, "Request Timeout");
+    /// `409 Conflict` (see RFC 9110, section 15.5.10)
+    (409, CONFLICT, "Conflict");
+    /// `410 Gone` (see RFC 9110, section 15.5.11)
+    (410, GONE, "Gone");
+    /// `411 Length Required` (see RFC 9110, section 15.5.12)
+    (411, LENGTH_REQUIRED, "Length Required");
+    /// `412 Precondition Failed` (see RFC 9110, section 15.5.13)
+    (412, PRECONDITION_FAILED, "Precondition Failed");
+    /// `413 Content Too Large` (see RFC 9110, section 15.5.14)
+    (413, CONTENT_TOO_LARGE, "Content Too Large");
+    /// `414 URI Too Long` (see RFC 9110, section 15.5.15)
+    (414, URI_TOO_LONG, "URI Too Long");
+    /// `415 Unsupported Media Type` (see RFC 9110, section 15.5.16)
+    (415, UNSUPPORTED_MEDIA_TYPE, "Unsupported Media Type");
+    /// `416 Range Not Satisfiable` (see RFC 9110, section 15.5.17)
+    (416, RANGE_NOT_SATISFIABLE, "Range Not Satisfiable");
+    /// `417 Expectation Failed` (see RFC 9110, section 15.5.18)
+    (417, EXPECTATION_FAILED, "Expectation Failed");
+    /// `421 Misdirected Request` (see RFC 9110, section 15.5.20)
+    (421, MISDIRECTED_REQUEST, "Misdirected Request");
+    /// `422 Unprocessable Content` (see RFC 9110, section 15.5.21)
+    (422, UNPROCESSABLE_CONTENT, "Unprocessable Content");
+    /// `423 Locked` (see RFC 4918, section 11.3)
+    (423, LOCKED, "Locked");
+    /// `424 Failed Dependency` (see RFC 4918, section 11.4)
+    (424, FAILED_DEPENDENCY, "Failed Dependency");
+    /// `425 Too Early` (see RFC 8470, section 5.2)
+    (425, TOO_EARLY, "Too Early");
+    /// `426 Upgrade Required` (see RFC 9110, section 15.5.22)
+    (426, UPGRADE_REQUIRED, "Upgrade Required");
+    /// `428 Precondition Required` (see RFC 6585, section 3)
+    (428, PRECONDITION_REQUIRED, "Precondition Required");
+    /// `429 Too Many Requests` (see RFC 6585, section 4)
+    (429, TOO_MANY_REQUESTS, "Too Many Requests");
+    /// `431 Request Header Fields Too Large` (see RFC 6585, section 5)
+    (431, REQUEST_HEADER_FIELDS_TOO_LARGE, "Request Header Fields Too Large");
+    /// `451 Unavailable For Legal Reasons` (see RFC 7725, section 3)
+    (451, UNAVAILABLE_FOR_LEGAL_REASONS, "Unavailable For Legal Reasons");
+
+    /// `500 Internal Server Error` (see RFC 9110, section 15.6.1)
+    (500, INTERNAL_SERVER_ERROR, "Internal Server Error");
+    /// `501 Not Implemented` (see RFC 9110, section 15.6.2)
+    (501, NOT_IMPLEMENTED, "Not Implemented");
+    /// `502 Bad Gateway` (see RFC 9110, section 15.6.3)
+    (502, BAD_GATEWAY, "Bad Gateway");
+    /// `503 Service Unavailable` (see RFC 9110, section 15.6.4)
+    (503, SERVICE_UNAVAILABLE, "Service Unavailable");
+    /// `504 Gateway Timeout` (see RFC 9110, section 15.6.5)
+    (504, GATEWAY_TIMEOUT, "Gateway Timeout");
+    /// `505 HTTP Version Not Supported` (see RFC 9110, section 15.6.6)
+    (505, HTTP_VERSION_NOT_SUPPORTED, "HTTP Version Not Supported");
+    /// `506 Variant Also Negotiates` (see RFC 2295, section 8.1)
+    (506, VARIANT_ALSO_NEGOTIATES, "Variant Also Negotiates");
+    /// `507 Insufficient Storage` (see RFC 4918, section 11.5)
+    (507, INSUFFICIENT_STORAGE, "Insufficient Storage");
+    /// `508 Loop Detected` (see RFC 5842, section 7.2)
+    (508, LOOP_DETECTED, "Loop Detected");
+    /// `510 Not Extended` (see RFC 2774, section 7)
+    (510, NOT_EXTENDED, "Not Extended");
+    /// `511 Network Authentication Required` (see RFC 6585, section 6)
+    (511, NETWORK_AUTHENTICATION_REQUIRED, "Network Authentication Required");
+}
@@ -0,0 +1,185 @@
+//! HTTP request targets
+
+use crate::{authority::Authority, error::Error, grammar, method::Method};
+
+/// A request target (see RFC 9112, section 3.2)
+///
+/// ## Note
+/// Only absolute-form targets with an authority component (i.e. `scheme://authority...`) are supported; this covers
+/// all `http` and `https` URIs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RequestTarget<'a> {
+    /// An origin-form target like `/path?query` (see RFC 9112, section 3.2.1)
+    Origin {
+        /// The absolute path
+        path: &'a [u8],
+        /// The query without the leading `?` if any
+        query: Option<&'a [u8]>,
+    },
+    /// An absolute-form target like `http://host/path?query` (see RFC 9112, section 3.2.2)
+    Absolute {
+        /// The scheme
+        scheme: &'a [u8],
+        /// The authority
+        authority: &'a [u8],
+        /// The path (may be empty)
+        path: &'a [u8],
+        /// The query without the leading `?` if any
+        query: Option<&'a [u8]>,
+    },
+    /// An authority-form target like `host:port` for `CONNECT` requests (see RFC 9112, section 3.2.3)
+    Authority(&'a [u8]),
+    /// The asterisk-form target `*` for server-wide `OPTIONS` requests (see RFC 9112, section 3.2.4)
+    Asterisk,
+}
+impl<'a> RequestTarget<'a> {
+    /// Parses the request target `bytes` of a request with the given `method`
+    ///
+    /// ## Note
+    /// The form is validated against the method: `CONNECT` requires the authority-form, and the asterisk-form is only
+    /// allowed for `OPTIONS`. The offset of the returned [`Error::InvalidStartLine`] is relative to `bytes`.
+    pub fn parse(bytes: &'a [u8], method: &Method) -> Result<Self, Error> {
+        // Validate the characters; fragments are never sent
+        grammar::validate_request_target(bytes).map_err(|offset| Error::InvalidStartLine { offset })?;
+        if let Some(offset) = bytes.iter().position(|byte| *byte == b'#') {
+            return Err(Error::InvalidStartLine { offset });
+        }
+
+        // Select the form
+        match (method, bytes) {
+            (Method::Connect, authority) => Self::parse_authority_form(authority),
+            (Method::Options, b"*") => Ok(Self::Asterisk),
+            (_, [b'/', ..]) => {
+                let (path, query) = Self::split_query(bytes);
+                Ok(Self::Origin { path, query })
+            }
+            _ => Self::parse_absolute_form(bytes),
+        }
+    }
+    /// Parses an authority-form target
+    fn parse_authority_form(bytes: &'a [u8]) -> Result<Self, Error> {
+        // Reject userinfo and path delimiters
+        if let Some(offset) = bytes.iter().position(|byte| matches!(byte, b'/' | b'?' | b'@')) {
+            return Err(Error::InvalidStartLine { offset });
+        }
+
+        // Require a non-empty host and a numeric port
+        let Some(separator) = bytes.iter().rposition(|byte| *byte == b':') else {
+            return Err(Error::InvalidStartLine { offset: bytes.len() });
+        };
+        let port = &bytes[separator + 1..];
+        match port.iter().position(|byte| !byte.is_ascii_digit()) {
+            _ if separator == 0 => Err(Error::InvalidStartLine { offset: 0 }),
+            _ if port.is_empty() => Err(Error::InvalidStartLine { offset: bytes.len() }),
+            Some(offset) => Err(Error::InvalidStartLine { offset: separator + 1 + offset }),
+            None => Ok(Self::Authority(bytes)),
+        }
+    }
+    /// Parses an absolute-form target
+    fn parse_absolute_form(bytes: &'a [u8]) -> Result<Self, Error> {
+        // Split and validate the scheme
+        let separator = bytes.iter().position(|byte| *byte == b':').unwrap_or(bytes.len());
+        let scheme = &bytes[..separator];
+        grammar::validate_scheme(scheme).map_err(|offset| Error::InvalidStartLine { offset })?;
+        let Some(rest) = bytes[separator..].strip_prefix(b"://") else {
+            return Err(Error::InvalidStartLine { offset: separator });
+        };
+
+        // Split the authority and reject userinfo
+        let authority_start = separator + 3;
+        let authority_len = rest.iter().position(|byte| matches!(byte, b'/' | b'?')).unwrap_or(rest.len());
+        let (authority, path_and_query) = rest.split_at(authority_len);
+        if let Some(offset) = authority.iter().position(|byte| *byte == b'@') {
+            return Err(Error::InvalidStartLine { offset: authority_start + offset });
+        }
+        if authority.is_empty() {
+            return Err(Error::InvalidStartLine { offset: authority_start });
+        }
+
+        let (path, query) = Self::split_query(path_and_query);
+        Ok(Self::Absolute { scheme, authority, path, query })
+    }
+    /// Splits `bytes` into path and optional query
+    fn split_query(bytes: &'a [u8]) -> (&'a [u8], Option<&'a [u8]>) {
+        match bytes.iter().position(|byte| *byte == b'?') {
+            Some(separator) => (&bytes[..separator], Some(&bytes[separator + 1..])),
+            None => (bytes, None),
+        }
+    }
+
+    /// The scheme if the target is in absolute-form
+    pub fn scheme(&self) -> Option<&'a [u8]> {
+        match self {
+            Self::Absolute { scheme, .. } => Some(scheme),
+            _ => None,
+        }
+    }
+    /// The authority if the target is in absolute- or authority-form
+    pub fn authority(&self) -> Option<&'a [u8]> {
+        match self {
+            Self::Absolute { authority, .. } | Self::Authority(authority) => Some(authority),
+            _ => None,
+        }
+    }
+    /// The authority as [`Authority`] if the target is in absolute- or authority-form
+    ///
+    /// ## Note
+    /// The offset of the returned [`Error::InvalidStartLine`] is relative to the target.
+    pub fn authority_typed(&self) -> Result<Option<Authority<'a>>, Error> {
+        // Get the authority and its offset within the target
+        let (authority, offset) = match self {
+            Self::Absolute { scheme, authority, .. } => (*authority, scheme.len() + 3),
+            Self::Authority(authority) => (*authority, 0),
+            Self::Origin { .. } | Self::Asterisk => return Ok(None),
+        };
+
+        // Parse the authority
+        match Authority::parse(authority) {
+            Ok(authority) => Ok(Some(authority)),
+            Err(Error::InvalidFieldValue { offset: error_offset }) => {
+                Err(Error::InvalidStartLine { offset: offset + error_offset })
+            }
+            Err(error) => Err(error),
+        }
+    }
+    /// The path (or an empty slice if the target has no path component)
+    pub fn path(&self) -> &'a [u8] {
+        match self {
+            Self::Origin { path, .. } | Self::Absolute { path, .. } => path,
+            Self::Authority(_) | Self::Asterisk => b"",
+        }
+    }
+    /// The query without the leading `?` if any
+    pub fn query(&self) -> Option<&'a [u8]> {
+        match self {
+            Self::Origin { query, .. } | Self::Absolute { query, .. } => *query,
+            Self::Authority(_) | Self::Asterisk => None,
+        }
+    }
+
+    /// Reconstructs the effective request URI (see RFC 9112, section 3.3)
+    ///
+    /// `scheme` is the scheme of the connection (e.g. `https` if the request was received over TLS) and `host` the
+    /// value of the `Host` field if any; both are ignored if the target is in absolute-form.
+    ///
+    /// ## Note
+    /// Returns `None` if the target is not in absolute- or authority-form and `host` is absent or empty, since the
+    /// authority cannot be determined in this case.
+    pub fn effective_uri(&self, scheme: &[u8], host: Option<&[u8]>) -> Option<Vec<u8>> {
+        // Select the scheme and authority
+        let (scheme, authority) = match (self.scheme(), self.authority(), host) {
+            (Some(scheme), Some(authority), _) => (scheme, authority),
+            (None, Some(authority), _) => (scheme, authority),
+            (_, None, Some(host)) if !host.is_empty() => (scheme, host),
+            _ => return None,
+        };
+
+        // Assemble the URI
+        let mut uri = [scheme, b"://", authority, self.path()].concat();
+        if let Some(query) = self.query() {
+            uri.push(b'?');
+            uri.extend_from_slice(query);
+        }
+        Some(uri)
+    }
+}
@@ -0,0 +1,327 @@
+//! Typed header fields
+
+use crate::{authority::Authority, date::HttpDate, error::Error, grammar, mediatype::MediaType, rules::ListElements};
+use std::str;
+
+/// A header field with typed semantics
+///
+/// ## Note
+/// Use [`crate::HeaderFields::typed_get`] and [`crate::HeaderFields::typed_set`] to access typed fields.
+pub trait TypedHeader
+where
+    Self: Sized,
+{
+    /// The field name
+    const NAME: &'static str;
+
+    /// Decodes the field from all values of the field in their order of occurrence
+    ///
+    /// ## Note
+    /// `values` is never empty if called via [`crate::HeaderFields::typed_get`].
+    fn decode(values: &[&[u8]]) -> Result<Self, Error>;
+    /// Encodes the field into a single valid field value
+    fn encode(&self) -> Vec<u8>;
+}
+
+/// The `Content-Length` field (see RFC 9110, section 8.6)
+///
+/// ## Note
+/// Repeated fields and comma-separated lists with identical values are decoded as a single length; any other
+/// combination, as well as a non-numeric value, is an [`Error::InvalidContentLength`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ContentLength(pub u64);
+impl TypedHeader for ContentLength {
+    const NAME: &'static str = "content-length";
+
+    fn decode(values: &[&[u8]]) -> Result<Self, Error> {
+        let mut content_length = None;
+        for value in values.iter().flat_map(|value| value.split(|b| *b == b',')) {
+            // Parse the length
+            let value = value.trim_ascii();
+            let length = match value.iter().all(u8::is_ascii_digit) {
+                true => str::from_utf8(value).ok().and_then(|value| value.parse().ok()),
+                false => None,
+            };
+
+            // Ensure that all values are identical
+            match (content_length, length) {
+                (_, None) => return Err(Error::InvalidContentLength),
+                (Some(content_length), Some(length)) if content_length != length => {
+                    return Err(Error::InvalidContentLength)
+                }
+                (_, length) => content_length = length,
+            }
+        }
+        content_length.map(Self).ok_or(Error::InvalidContentLength)
+    }
+    fn encode(&self) -> Vec<u8> {
+        self.0.to_string().into_bytes()
+    }
+}
+
+/// The `Content-Type` field (see RFC 9110, section 8.3)
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ContentType(Vec<u8>);
+impl ContentType {
+    /// Creates a new content type field or fails if `value` is not a valid field value
+    pub fn new(value: &[u8]) -> Result<Self, Error> {
+        Ok(Self(validate_value(value)?.to_vec()))
+    }
+    /// The raw media type
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+    /// The parsed media type
+    pub fn media_type(&self) -> Result<MediaType, Error> {
+        MediaType::parse(&self.0)
+    }
+}
+impl From<MediaType> for ContentType {
+    fn from(media_type: MediaType) -> Self {
+        Self(media_type.to_bytes())
+    }
+}
+impl TypedHeader for ContentType {
+    const NAME: &'static str = "content-type";
+
+    fn decode(values: &[&[u8]]) -> Result<Self, Error> {
+        Self::new(singleton(values)?)
+    }
+    fn encode(&self) -> Vec<u8> {
+        self.0.clone()
+    }
+}
+
+/// The `Host` field (see RFC 9110, section 7.2)
+///
+/// ## Note
+/// An empty `Host` field value is rejected, since it cannot be routed anyway. The host part of the authority is
+/// [`crate::Host`].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HostField(Vec<u8>);
+impl HostField {
+    /// Creates a new host field or fails if `value` is not a valid authority
+    pub fn new(value: &[u8]) -> Result<Self, Error> {
+        Authority::parse(value)?;
+        Ok(Self(value.to_vec()))
+    }
+    /// The raw authority
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+    /// The parsed authority
+    pub fn authority(&self) -> Authority<'_> {
+        Authority::parse(&self.0).expect("host field must be a valid authority")
+    }
+}
+impl TypedHeader for HostField {
+    const NAME: &'static str = "host";
+
+    fn decode(values: &[&[u8]]) -> Result<Self, Error> {
+        Self::new(singleton(values)?)
+    }
+    fn encode(&self) -> Vec<u8> {
+        self.0.clone()
+    }
+}
+
+/// The `Connection` field (see RFC 9110, section 7.6.1)
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Connection(Vec<Vec<u8>>);
+impl Connection {
+    /// Creates a new connection field or fails if an option is not a valid token
+    pub fn new<'a, I>(options: I) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = &'a [u8]>,
+    {
+        let options = options.into_iter().map(|option| validate_token(option).map(<[u8]>::to_vec));
+        Ok(Self(options.collect::<Result<_, _>>()?))
+    }
+    /// Creates a new connection field with the `close` option
+    pub fn close() -> Self {
+        Self(vec![b"close".to_vec()])
+    }
+    /// Creates a new connection field with the `keep-alive` option
+    pub fn keep_alive() -> Self {
+        Self(vec![b"keep-alive".to_vec()])
+    }
+
+    /// The connection options in their order of occurrence
+    pub fn options(&self) -> impl Iterator<Item = &[u8]> {
+        self.0.iter().map(Vec::as_slice)
+    }
+    /// Whether the field contains the given option or not (case-insensitive)
+    pub fn contains(&self, option: &[u8]) -> bool {
+        self.options().any(|candidate| candidate.eq_ignore_ascii_case(option))
+    }
+}
+impl TypedHeader for Connection {
+    const NAME: &'static str = "connection";
+
+    fn decode(values: &[&[u8]]) -> Result<Self, Error> {
+        Self::new(list_elements(values))
+    }
+    fn encode(&self) -> Vec<u8> {
+        self.0.join(b", ".as_slice())
+    }
+}
+
+/// The `Transfer-Encoding` field (see RFC 9112, section 6.1)
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TransferEncoding(Vec<Vec<u8>>);
+impl TransferEncoding {
+    /// Creates a new transfer encoding field from transfer codings with optional parameters (e.g. `gzip` or
+    /// `chunked`)
+    ///
+    /// ## Note
+    /// An empty list, a coding without valid name, or applying `chunked` more than once or not as final transfer coding
+    /// is an [`Error::InvalidTransferEncoding`].
+    pub fn new<'a, I>(codings: I) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = &'a [u8]>,
+    {
+        // Validate the position of `chunked`
+        let this = Self::new_unordered(codings)?;
+        let chunked = this.codings().filter(|name| name.eq_ignore_ascii_case(b"chunked")).count();
+        match chunked {
+            0 => Ok(this),
+            1 if this.is_chunked() => Ok(this),
+            _ => Err(Error::InvalidTransferEncoding),
+        }
+    }
+    /// Creates a new transfer encoding field like [`TransferEncoding::new`], but without validating the position of
+    /// `chunked`
+    fn new_unordered<'a, I>(codings: I) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = &'a [u8]>,
+    {
+        let mut validated = Vec::new();
+        for coding in codings {
+            let name = coding.split(|b| *b == b';').next().unwrap_or_default().trim_ascii();
+            grammar::validate_token(name).map_err(|_| Error::InvalidTransferEncoding)?;
+            validate_value(coding).map_err(|_| Error::InvalidTransferEncoding)?;
+            validated.push(coding.to_vec());
+        }
+        match validated.is_empty() {
+            true => Err(Error::InvalidTransferEncoding),
+            false => Ok(Self(validated)),
+        }
+    }
+    /// Decodes the field like [`TypedHeader::decode`], but without validating the position of `chunked` (e.g. for
+    /// responses, see RFC 9112, section 6.3)
+    pub(crate) fn decode_unordered(values: &[&[u8]]) -> Result<Self, Error> {
+        Self::new_unordered(list_elements(values))
+    }
+    /// Creates a new transfer encoding field with the `chunked` coding
+    pub fn chunked() -> Self {
+        Self(vec![b"chunked".to_vec()])
+    }
+
+    /// The transfer coding names without parameters in their order of application
+    pub fn codings(&self) -> impl Iterator<Item = &[u8]> {
+        let codings = self.0.iter().map(|coding| coding.split(|b| *b == b';').next().unwrap_or_default());
+        codings.map(<[u8]>::trim_ascii)
+    }
+    /// Whether the final transfer coding is `chunked` or not
+    pub fn is_chunked(&self) -> bool {
+        self.codings().last().is_some_and(|name| name.eq_ignore_ascii_case(b"chunked"))
+    }
+}
+impl TypedHeader for TransferEncoding {
+    const NAME: &'static str = "transfer-encoding";
+
+    fn decode(values: &[&[u8]]) -> Result<Self, Error> {
+        Self::new(list_elements(values))
+    }
+    fn encode(&self) -> Vec<u8> {
+        self.0.join(b", ".as_slice())
+    }
+}
+
+/// The `Date` field (see RFC 9110, section 6.6.1)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date(pub HttpDate);
+impl TypedHeader for Date {
+    const NAME: &'static str = "date";
+
+    fn decode(values: &[&[u8]]) -> Result<Self, Error> {
+        HttpDate::parse(singleton(values)?).map(Self)
+    }
+    fn encode(&self) -> Vec<u8> {
+        self.0.to_string().into_bytes()
+    }
+}
+
+/// The `Location` field (see RFC 9110, section 10.2.2)
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Location(Vec<u8>);
+impl Location {
+    /// Creates a new location field or fails if `uri` is not a non-empty sequence of visible ASCII characters
+    pub fn new(uri: &[u8]) -> Result<Self, Error> {
+        grammar::validate_request_target(uri).map_err(|offset| Error::InvalidFieldValue { offset })?;
+        Ok(Self(uri.to_vec()))
+    }
+    /// The raw URI reference
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+impl TypedHeader for Location {
+    const NAME: &'static str = "location";
+
+    fn decode(values: &[&[u8]]) -> Result<Self, Error> {
+        Self::new(singleton(values)?)
+    }
+    fn encode(&self) -> Vec<u8> {
+        self.0.clone()
+    }
+}
+
+/// The `User-Agent` field (see RFC 9110, section 10.1.5)
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UserAgent(Vec<u8>);
+impl UserAgent {
+    /// Creates a new user agent field or fails if `value` is not a valid field value
+    pub fn new(value: &[u8]) -> Result<Self, Error> {
+        Ok(Self(validate_value(value)?.to_vec()))
+    }
+    /// The raw product identifiers and comments
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+impl TypedHeader for UserAgent {
+    const NAME: &'static str = "user-agent";
+
+    fn decode(values: &[&[u8]]) -> Result<Self, Error> {
+        Self::new(singleton(values)?)
+    }
+    fn encode(&self) -> Vec<u8> {
+        self.0.clone()
+    }
+}
+
+/// Gets the only value of a singleton field or fails if the field is absent or repeated
+fn singleton<'a>(values: &[&'a [u8]]) -> Result<&'a [u8], Error> {
+    match values {
+        [value] => Ok(value),
+        _ => Err(Error::InvalidFieldValue { offset: 0 }),
+    }
+}
+/// Validates that `value` is a non-empty field value
+fn validate_value(value: &[u8]) -> Result<&[u8], Error> {
+    match grammar::validate_field_value(value) {
+        _ if value.is_empty() => Err(Error::InvalidFieldValue { offset: 0 }),
+        Err(offset) => Err(Error::InvalidFieldValue { offset }),
+        Ok(()) => Ok(value),
+    }
+}
+/// Validates that `value` is a token
+fn validate_token(value: &[u8]) -> Result<&[u8], Error> {
+    grammar::validate_token(value).map_err(|offset| Error::InvalidFieldValue { offset })?;
+    Ok(value)
+}
+/// Splits the values of a list-based field into their non-empty, trimmed elements (see RFC 9110, section 5.6.1)
+fn list_elements<'a>(values: &'a [&'a [u8]]) -> impl Iterator<Item = &'a [u8]> {
+    values.iter().flat_map(|value| ListElements::new(value))
+}
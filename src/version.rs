@@ -0,0 +1,69 @@
+//! HTTP versions
+
+use crate::{bytetraits::IntoBytes, error::Error, grammar};
+use std::{
+    borrow::Cow,
+    fmt::{self, Display, Formatter},
+};
+
+/// A HTTP/1 version (see RFC 9112, section 2.3)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Version {
+    /// `HTTP/0.9` (i.e. simple requests without header fields)
+    Http09,
+    /// `HTTP/1.0` (see RFC 1945)
+    Http10,
+    /// `HTTP/1.1` (see RFC 9112)
+    Http11,
+}
+impl Version {
+    /// Parses a version from `bytes`
+    ///
+    /// ## Note
+    /// The offset of the returned [`Error::InvalidStartLine`] is relative to `bytes`. Syntactically valid but unsupported
+    /// versions (e.g. `HTTP/2.0`) are rejected with offset `0`.
+    pub fn parse(bytes: &[u8]) -> Result<Self, Error> {
+        grammar::validate_http_version(bytes).map_err(|offset| Error::InvalidStartLine { offset })?;
+        match bytes {
+            b"HTTP/0.9" => Ok(Self::Http09),
+            b"HTTP/1.0" => Ok(Self::Http10),
+            b"HTTP/1.1" => Ok(Self::Http11),
+            _ => Err(Error::InvalidStartLine { offset: 0 }),
+        }
+    }
+
+    /// The version token
+    pub const fn as_bytes(&self) -> &'static [u8] {
+        match self {
+            Self::Http09 => b"HTTP/0.9",
+            Self::Http10 => b"HTTP/1.0",
+            Self::Http11 => b"HTTP/1.1",
+        }
+    }
+
+    /// Whether connections are persistent by default or not (see RFC 9112, section 9.3)
+    ///
+    /// ## Note
+    /// An explicit `Connection` field overrides the default; see [`crate::Header::is_keep_alive`].
+    pub const fn is_keep_alive_default(&self) -> bool {
+        matches!(self, Self::Http11)
+    }
+    /// Whether requests must contain a `Host` field or not (see RFC 9112, section 3.2)
+    pub const fn requires_host(&self) -> bool {
+        matches!(self, Self::Http11)
+    }
+    /// Whether messages may have header fields or not
+    pub const fn has_fields(&self) -> bool {
+        !matches!(self, Self::Http09)
+    }
+}
+impl Display for Version {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", String::from_utf8_lossy(self.as_bytes()))
+    }
+}
+impl IntoBytes for Version {
+    fn into_bytes(self) -> Cow<'static, [u8]> {
+        Cow::Borrowed(self.as_bytes())
+    }
+}
@@ -0,0 +1,108 @@
+mod helpers;
+
+use http_tiny::{Authority, Error, Header, Host, Method, RequestTarget};
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+struct Test {
+    raw: &'static [u8],
+    host: Host<'static>,
+    port: Option<u16>,
+}
+impl Test {
+    fn test(self) {
+        let authority = Authority::parse(self.raw).expect("Failed to parse authority");
+        assert_eq!(
+            (self.host, self.port),
+            (authority.host(), authority.port()),
+            "Unexpected authority for {:?}",
+            String::from_utf8_lossy(self.raw)
+        );
+    }
+}
+#[test]
+fn test_parse() {
+    Test { raw: b"Example.com:8080", host: Host::RegName(b"Example.com"), port: Some(8080) }.test();
+    Test { raw: b"caf%C3%A9.test:", host: Host::RegName(b"caf%C3%A9.test"), port: None }.test();
+    Test { raw: b"192.0.2.1", host: Host::Ipv4(Ipv4Addr::new(192, 0, 2, 1)), port: None }.test();
+    Test {
+        raw: b"[2001:db8::1]:443",
+        host: Host::Ipv6 { address: Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1), zone: None },
+        port: Some(443),
+    }
+    .test();
+    Test {
+        raw: b"[fe80::1%25eth0]",
+        host: Host::Ipv6 { address: Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 1), zone: Some(b"eth0") },
+        port: None,
+    }
+    .test();
+}
+
+#[derive(Debug)]
+struct TestErr {
+    raw: &'static [u8],
+    error: Error,
+}
+impl TestErr {
+    fn test(self) {
+        let error = match Authority::parse(self.raw) {
+            Err(error) => error,
+            Ok(authority) => panic!("Unexpected `Ok` for {:?} ({authority:?})", String::from_utf8_lossy(self.raw)),
+        };
+        assert_eq!(
+            self.error.to_string(),
+            error.to_string(),
+            "Unexpected error for {:?}",
+            String::from_utf8_lossy(self.raw)
+        );
+    }
+}
+#[test]
+fn test_parse_err() {
+    TestErr { raw: b"", error: Error::InvalidFieldValue { offset: 0 } }.test();
+    TestErr { raw: b":80", error: Error::InvalidFieldValue { offset: 0 } }.test();
+    TestErr { raw: b"user:pass@example.com", error: Error::InvalidFieldValue { offset: 9 } }.test();
+    TestErr { raw: b"example.com:65536", error: Error::InvalidFieldValue { offset: 12 } }.test();
+    TestErr { raw: b"example.com:8o", error: Error::InvalidFieldValue { offset: 13 } }.test();
+    TestErr { raw: b"exa mple", error: Error::InvalidFieldValue { offset: 3 } }.test();
+    TestErr { raw: b"bad%2", error: Error::InvalidFieldValue { offset: 3 } }.test();
+    TestErr { raw: b"[::1", error: Error::InvalidFieldValue { offset: 0 } }.test();
+    TestErr { raw: b"[::g]", error: Error::InvalidFieldValue { offset: 1 } }.test();
+    TestErr { raw: b"[::1]x", error: Error::InvalidFieldValue { offset: 5 } }.test();
+    TestErr { raw: b"[fe80::1%eth0]", error: Error::InvalidFieldValue { offset: 8 } }.test();
+}
+
+#[test]
+fn test_normalized() {
+    // Authorities as (raw, default port, normalized)
+    let authorities: [(&[u8], u16, &[u8]); 5] = [
+        (b"WWW.Example.COM:80", 80, b"www.example.com"),
+        (b"www.example.com:8080", 80, b"www.example.com:8080"),
+        (b"www.example.com", 443, b"www.example.com"),
+        (b"[2001:DB8:0:0::1]:443", 443, b"[2001:db8::1]"),
+        (b"[fe80::1%25en1]:80", 443, b"[fe80::1%25en1]:80"),
+    ];
+    for (raw, default_port, normalized) in authorities {
+        let authority = Authority::parse(raw).expect("Invalid authority");
+        assert_eq!(normalized, authority.normalized(default_port).as_slice());
+    }
+    assert_eq!(443, Authority::parse(b"example.com").expect("Invalid authority").port_or(443));
+}
+
+#[test]
+fn test_sources() {
+    // Parse the host field
+    let raw = b"GET / HTTP/1.1\r\nHost: example.com:8080\r\n\r\n";
+    let header = Header::read(&mut helpers::source(raw)).expect("Failed to read header");
+    let host = header.fields().get("host").expect("Missing host field");
+    assert_eq!(Some(8080), Authority::parse(host).expect("Invalid authority").port());
+
+    // Parse the authority of request targets
+    let target = RequestTarget::parse(b"[::1]:8443", &Method::Connect).expect("Invalid target");
+    let authority = target.authority_typed().expect("Invalid authority").expect("Missing authority");
+    assert_eq!(Some(8443), authority.port());
+    let target = RequestTarget::parse(b"http://example.com:99999/", &Method::Get).expect("Invalid target");
+    assert!(matches!(target.authority_typed(), Err(Error::InvalidStartLine { offset: 19 })));
+    let target = RequestTarget::parse(b"/", &Method::Get).expect("Invalid target");
+    assert_eq!(None, target.authority_typed().expect("Invalid authority"));
+}
@@ -0,0 +1,117 @@
+mod helpers;
+
+use http_tiny::{ChunkedReader, ChunkedWriter, Error, HeaderFields, Limit, ReadOptions};
+use std::{
+    io::{BufReader, ErrorKind, Read, Write},
+    iter::FromIterator,
+};
+
+struct Test {
+    raw: &'static [u8],
+    body: Result<&'static [u8], ErrorKind>,
+    trailers: HeaderFields,
+}
+impl Test {
+    fn test(self) {
+        // Decode the body with different buffer sizes to test chunk boundaries
+        for capacity in [1, 3, 4096] {
+            let mut reader = ChunkedReader::new(BufReader::with_capacity(capacity, self.raw));
+            let mut body = Vec::new();
+            match (reader.read_to_end(&mut body), self.body) {
+                (Ok(_), Ok(expected)) => assert_eq!(expected, body.as_slice()),
+                (Err(error), Err(kind)) => {
+                    assert_eq!(kind, error.kind(), "Unexpected error {error:?}");
+                    continue;
+                }
+                (result, expected) => panic!("Unexpected result {result:?} (expected {expected:?})"),
+            }
+            assert_eq!(Some(&self.trailers), reader.trailers());
+
+            // Ensure that the subsequent data has not been consumed
+            let mut trailing = Vec::new();
+            reader.into_inner().read_to_end(&mut trailing).expect("Failed to read trailing data");
+            assert_eq!(b"NEXT", trailing.as_slice());
+        }
+    }
+}
+#[test]
+fn test() {
+    Test { raw: b"4\r\nWiki\r\n5\r\npedia\r\n0\r\n\r\nNEXT", body: Ok(b"Wikipedia"), trailers: HeaderFields::new() }
+        .test();
+    Test {
+        raw: b"a;name=value;quoted=\"a;b\"\r\n0123456789\r\n0\r\nExpires: never\r\nX-Checksum: 42\r\n\r\nNEXT",
+        body: Ok(b"0123456789"),
+        trailers: HeaderFields::from_iter([("Expires", "never"), ("X-Checksum", "42")]),
+    }
+    .test();
+    Test { raw: b"0\n\nNEXT", body: Ok(b""), trailers: HeaderFields::new() }.test();
+
+    Test { raw: b"4\r\nWik", body: Err(ErrorKind::UnexpectedEof), trailers: HeaderFields::new() }.test();
+    Test { raw: b"4\r\nWiki\r\n", body: Err(ErrorKind::UnexpectedEof), trailers: HeaderFields::new() }.test();
+    Test { raw: b"4\r\nWikipedia\r\n0\r\n\r\n", body: Err(ErrorKind::InvalidData), trailers: HeaderFields::new() }
+        .test();
+    Test { raw: b"x\r\nWiki\r\n0\r\n\r\n", body: Err(ErrorKind::InvalidData), trailers: HeaderFields::new() }.test();
+    Test { raw: b"-4\r\nWiki\r\n0\r\n\r\n", body: Err(ErrorKind::InvalidData), trailers: HeaderFields::new() }.test();
+    Test { raw: b"4 x\r\nWiki\r\n0\r\n\r\n", body: Err(ErrorKind::InvalidData), trailers: HeaderFields::new() }.test();
+    Test {
+        raw: b"10000000000000000\r\nWiki\r\n0\r\n\r\n",
+        body: Err(ErrorKind::InvalidData),
+        trailers: HeaderFields::new(),
+    }
+    .test();
+}
+
+#[test]
+fn test_long_line() {
+    let raw = [b"4;".as_slice(), &[b'x'; 8192], b"\r\nWiki\r\n0\r\n\r\n"].concat();
+    let error = ChunkedReader::new(raw.as_slice()).read_to_end(&mut Vec::new()).expect_err("Unexpected success");
+    assert_eq!(ErrorKind::InvalidData, error.kind());
+}
+
+#[test]
+fn test_trailer_limits() {
+    /// Decodes `raw` and returns the error
+    fn decode_error(raw: &[u8], options: ReadOptions) -> Error {
+        let error =
+            ChunkedReader::with_options(raw, options).read_to_end(&mut Vec::new()).expect_err("Unexpected success");
+        *error.into_inner().expect("Missing inner error").downcast::<Error>().expect("Unexpected error type")
+    }
+
+    // The default options limit the amount of trailer fields
+    let trailers = "X-Trailer: value\r\n".repeat(200_000);
+    let raw = [b"4\r\nWiki\r\n0\r\n".as_slice(), trailers.as_bytes(), b"\r\n"].concat();
+    let error = decode_error(&raw, ReadOptions::default());
+    assert!(matches!(error, Error::LimitExceeded { limit: Limit::FieldCount, offset: 2304 }), "{error:?}");
+
+    // The line and size limits apply to the trailer section, too
+    let options = ReadOptions { max_field_line: 16, ..Default::default() };
+    let error = decode_error(b"0\r\nX-Long-Trailer: value\r\n\r\n", options);
+    assert!(matches!(error, Error::LimitExceeded { limit: Limit::FieldLine, offset: 0 }), "{error:?}");
+    let options = ReadOptions { max_header_size: 32, ..Default::default() };
+    let error = decode_error(b"0\r\nX-A: value\r\nX-B: value\r\nX-C: value\r\n\r\n", options);
+    assert!(matches!(error, Error::LimitExceeded { limit: Limit::HeaderSize, offset: 24 }), "{error:?}");
+}
+
+#[test]
+fn test_writer() {
+    // Write some chunks without trailers
+    let mut writer = ChunkedWriter::new(Vec::new());
+    writer.write_all(b"Wiki").expect("Failed to write chunk");
+    writer.write_all(b"").expect("Failed to write chunk");
+    writer.write_all(b"pedia in chunks").expect("Failed to write chunk");
+    let encoded = writer.finish().expect("Failed to finish body");
+    assert_eq!(b"4\r\nWiki\r\nF\r\npedia in chunks\r\n0\r\n\r\n", encoded.as_slice());
+
+    // Write a chunk with trailers and decode it again
+    let trailers = HeaderFields::from_iter([("X-Checksum", "42")]);
+    let mut writer = ChunkedWriter::new(Vec::new());
+    writer.write_all(b"Wikipedia").expect("Failed to write chunk");
+    let encoded = writer.finish_with_trailers(&trailers).expect("Failed to finish body");
+    assert_eq!(b"9\r\nWikipedia\r\n0\r\nX-Checksum: 42\r\n\r\n", encoded.as_slice());
+
+    let mut reader = ChunkedReader::new(helpers::source(encoded));
+    let mut decoded = Vec::new();
+    reader.read_to_end(&mut decoded).expect("Failed to decode body");
+    assert_eq!(b"Wikipedia", decoded.as_slice());
+    assert_eq!(Some(&trailers), reader.trailers());
+}
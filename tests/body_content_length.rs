@@ -0,0 +1,108 @@
+mod helpers;
+
+use http_tiny::{ContentLengthReader, ContentLengthWriter, Error, Header};
+use std::io::{BufRead, ErrorKind, Read, Write};
+
+struct Test {
+    raw: &'static [u8],
+    body: Result<&'static [u8], ErrorKind>,
+    trailing: &'static [u8],
+}
+impl Test {
+    fn test(self) {
+        // Read the header and the body
+        let mut source = helpers::source(self.raw);
+        let header = Header::read(&mut source).expect("Failed to read header");
+        let mut reader = match ContentLengthReader::from_header(&header, &mut source) {
+            Ok(reader) => reader,
+            Err(error) => return assert_eq!(self.body, Err(error.kind()), "Unexpected error {error:?}"),
+        };
+        let mut body = Vec::new();
+        match (reader.read_to_end(&mut body), self.body) {
+            (Ok(_), Ok(expected)) => assert_eq!(expected, body.as_slice()),
+            (Err(error), Err(kind)) => return assert_eq!(kind, error.kind(), "Unexpected error {error:?}"),
+            (result, expected) => panic!("Unexpected result {result:?} (expected {expected:?})"),
+        }
+        assert_eq!(0, reader.remaining());
+
+        // Ensure that the subsequent data has not been consumed
+        let mut trailing = Vec::new();
+        source.read_to_end(&mut trailing).expect("Failed to read trailing data");
+        assert_eq!(self.trailing, trailing.as_slice());
+    }
+}
+#[test]
+fn test() {
+    Test {
+        raw: b"POST / HTTP/1.1\r\nContent-Length: 4\r\n\r\nTestGET / HTTP/1.1\r\n\r\n",
+        body: Ok(b"Test"),
+        trailing: b"GET / HTTP/1.1\r\n\r\n",
+    }
+    .test();
+    Test { raw: b"POST / HTTP/1.1\r\n\r\nTest", body: Ok(b""), trailing: b"Test" }.test();
+    Test {
+        raw: b"POST / HTTP/1.1\r\nContent-Length: 4\r\ncontent-length: 4, 4\r\n\r\nTest",
+        body: Ok(b"Test"),
+        trailing: b"",
+    }
+    .test();
+
+    Test {
+        raw: b"POST / HTTP/1.1\r\nContent-Length: 5\r\n\r\nTest",
+        body: Err(ErrorKind::UnexpectedEof),
+        trailing: b"",
+    }
+    .test();
+    Test {
+        raw: b"POST / HTTP/1.1\r\nContent-Length: 4\r\nContent-Length: 5\r\n\r\nTest",
+        body: Err(ErrorKind::InvalidData),
+        trailing: b"",
+    }
+    .test();
+    Test {
+        raw: b"POST / HTTP/1.1\r\nContent-Length: +4\r\n\r\nTest",
+        body: Err(ErrorKind::InvalidData),
+        trailing: b"",
+    }
+    .test();
+    Test { raw: b"POST / HTTP/1.1\r\nContent-Length: \r\n\r\nTest", body: Err(ErrorKind::InvalidData), trailing: b"" }
+        .test();
+    Test {
+        raw: b"POST / HTTP/1.1\r\nContent-Length: 18446744073709551616\r\n\r\nTest",
+        body: Err(ErrorKind::InvalidData),
+        trailing: b"",
+    }
+    .test();
+}
+
+#[test]
+fn test_buffered() {
+    let mut reader = ContentLengthReader::new(b"Test\r\nBODY" as &[u8], 8);
+    assert_eq!(b"Test\r\nBO", reader.fill_buf().expect("Failed to fill buffer"));
+    reader.consume(6);
+    let mut body = String::new();
+    reader.read_to_string(&mut body).expect("Failed to read body");
+    assert_eq!("BO", body);
+}
+
+#[test]
+fn test_writer() {
+    // Write the exact length
+    let mut writer = ContentLengthWriter::new(Vec::new(), 4);
+    writer.write_all(b"Te").expect("Failed to write body");
+    writer.write_all(b"st").expect("Failed to write body");
+    assert_eq!(b"Test", writer.finish().expect("Failed to finish body").as_slice());
+
+    // Write too many or too few bytes
+    let mut writer = ContentLengthWriter::new(Vec::new(), 4);
+    let error = writer.write_all(b"Test!").expect_err("Unexpected success for oversized body");
+    assert_eq!(ErrorKind::InvalidInput, error.kind());
+    writer.write_all(b"Tes").expect("Failed to write body");
+    assert_eq!(1, writer.remaining());
+    assert_eq!(ErrorKind::InvalidInput, writer.finish().expect_err("Unexpected success for short body").kind());
+
+    // Reject invalid lengths
+    let header = Header::read(&mut helpers::source(b"HTTP/1.1 200 OK\r\nContent-Length: x\r\n\r\n"))
+        .expect("Failed to read header");
+    assert!(matches!(ContentLengthWriter::from_header(&header, Vec::<u8>::new()), Err(Error::InvalidContentLength)));
+}
@@ -0,0 +1,165 @@
+mod helpers;
+
+use http_tiny::{Error, Framing, Header};
+
+struct Test {
+    raw: &'static [u8],
+    request_method: Option<&'static [u8]>,
+    framing: Framing,
+}
+impl Test {
+    fn test(self) {
+        let header = Header::read(&mut helpers::source(self.raw)).expect("Failed to read header");
+        let framing = header.body_framing(self.request_method).expect("Failed to determine framing");
+        assert_eq!(self.framing, framing, "Unexpected framing for header: {}", String::from_utf8_lossy(self.raw));
+    }
+}
+
+#[derive(Debug)]
+struct TestErr {
+    raw: &'static [u8],
+    request_method: Option<&'static [u8]>,
+    error: Error,
+}
+impl TestErr {
+    fn test(self) {
+        let header = Header::read(&mut helpers::source(self.raw)).expect("Failed to read header");
+        let error = match header.body_framing(self.request_method) {
+            Err(error) => error,
+            Ok(framing) => panic!("Unexpected `Ok` for header: {} ({framing:?})", String::from_utf8_lossy(self.raw)),
+        };
+        assert_eq!(
+            self.error.to_string(),
+            error.to_string(),
+            "Unexpected error for header: {}",
+            String::from_utf8_lossy(self.raw)
+        );
+    }
+}
+
+#[test]
+fn test_request() {
+    Test { raw: b"GET / HTTP/1.1\r\nHost: localhost\r\n\r\n", request_method: None, framing: Framing::None }.test();
+    Test {
+        raw: b"POST / HTTP/1.1\r\nContent-Length: 42\r\n\r\n",
+        request_method: None,
+        framing: Framing::ContentLength(42),
+    }
+    .test();
+    Test {
+        raw: b"POST / HTTP/1.1\r\nTransfer-Encoding: gzip\r\nTransfer-Encoding: Chunked\r\n\r\n",
+        request_method: None,
+        framing: Framing::Chunked,
+    }
+    .test();
+
+    // Request smuggling vectors
+    TestErr {
+        raw: b"POST / HTTP/1.1\r\nContent-Length: 42\r\nTransfer-Encoding: chunked\r\n\r\n",
+        request_method: None,
+        error: Error::InvalidTransferEncoding,
+    }
+    .test();
+    TestErr {
+        raw: b"POST / HTTP/1.1\r\nContent-Length: 42\r\nContent-Length: 43\r\n\r\n",
+        request_method: None,
+        error: Error::InvalidContentLength,
+    }
+    .test();
+    TestErr {
+        raw: b"POST / HTTP/1.1\r\nTransfer-Encoding: chunked, gzip\r\n\r\n",
+        request_method: None,
+        error: Error::InvalidTransferEncoding,
+    }
+    .test();
+    TestErr {
+        raw: b"POST / HTTP/1.1\r\nTransfer-Encoding: chunked, chunked\r\n\r\n",
+        request_method: None,
+        error: Error::InvalidTransferEncoding,
+    }
+    .test();
+    TestErr {
+        raw: b"POST / HTTP/1.1\r\nTransfer-Encoding: gzip\r\n\r\n",
+        request_method: None,
+        error: Error::InvalidTransferEncoding,
+    }
+    .test();
+    TestErr {
+        raw: b"POST / HTTP/1.1\r\nTransfer-Encoding: ,\r\n\r\n",
+        request_method: None,
+        error: Error::InvalidTransferEncoding,
+    }
+    .test();
+    TestErr {
+        raw: b"POST / HTTP/1.0\r\nTransfer-Encoding: chunked\r\n\r\n",
+        request_method: None,
+        error: Error::InvalidTransferEncoding,
+    }
+    .test();
+}
+
+#[test]
+fn test_response() {
+    Test {
+        raw: b"HTTP/1.1 200 OK\r\nContent-Length: 42\r\n\r\n",
+        request_method: Some(b"GET"),
+        framing: Framing::ContentLength(42),
+    }
+    .test();
+    Test {
+        raw: b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n",
+        request_method: None,
+        framing: Framing::Chunked,
+    }
+    .test();
+    Test {
+        raw: b"HTTP/1.1 200 OK\r\nTransfer-Encoding: gzip\r\n\r\n",
+        request_method: None,
+        framing: Framing::UntilClose,
+    }
+    .test();
+    Test {
+        raw: b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked, gzip\r\n\r\n",
+        request_method: None,
+        framing: Framing::UntilClose,
+    }
+    .test();
+    Test { raw: b"HTTP/1.1 200 OK\r\n\r\n", request_method: None, framing: Framing::UntilClose }.test();
+    TestErr {
+        raw: b"HTTP/1.0 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n",
+        request_method: None,
+        error: Error::InvalidTransferEncoding,
+    }
+    .test();
+    TestErr {
+        raw: b"HTTP/1.1 200 OK\r\nTransfer-Encoding: ,\r\n\r\n",
+        request_method: None,
+        error: Error::InvalidTransferEncoding,
+    }
+    .test();
+
+    // Responses without body
+    Test {
+        raw: b"HTTP/1.1 200 OK\r\nContent-Length: 42\r\n\r\n",
+        request_method: Some(b"HEAD"),
+        framing: Framing::None,
+    }
+    .test();
+    Test {
+        raw: b"HTTP/1.1 200 Connection Established\r\n\r\n",
+        request_method: Some(b"CONNECT"),
+        framing: Framing::None,
+    }
+    .test();
+    Test {
+        raw: b"HTTP/1.1 407 Proxy Authentication Required\r\nContent-Length: 0\r\n\r\n",
+        request_method: Some(b"CONNECT"),
+        framing: Framing::ContentLength(0),
+    }
+    .test();
+    for status in [b"100 Continue" as &[u8], b"101 Switching Protocols", b"204 No Content", b"304 Not Modified"] {
+        let raw = [b"HTTP/1.1 " as &[u8], status, b"\r\nTransfer-Encoding: chunked\r\n\r\n"].concat();
+        let header = Header::read(&mut helpers::source(raw)).expect("Failed to read header");
+        assert_eq!(Framing::None, header.body_framing(Some(b"GET")).expect("Failed to determine framing"));
+    }
+}
@@ -0,0 +1,108 @@
+mod helpers;
+
+use http_tiny::{Error, Header, HttpDate};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// `Sun, 06 Nov 1994 08:49:37 GMT` as seconds since the UNIX epoch
+const EXAMPLE_SECS: u64 = 784_111_777;
+
+struct Test {
+    raw: &'static [u8],
+    secs: u64,
+}
+impl Test {
+    fn test(self) {
+        let date = HttpDate::parse(self.raw).expect("Failed to parse date");
+        assert_eq!(
+            UNIX_EPOCH + Duration::from_secs(self.secs),
+            SystemTime::from(date),
+            "Unexpected date for {:?}",
+            String::from_utf8_lossy(self.raw)
+        );
+    }
+}
+#[test]
+fn test_parse() {
+    // All three formats
+    Test { raw: b"Sun, 06 Nov 1994 08:49:37 GMT", secs: EXAMPLE_SECS }.test();
+    Test { raw: b"Sunday, 06-Nov-94 08:49:37 GMT", secs: EXAMPLE_SECS }.test();
+    Test { raw: b"Sun Nov  6 08:49:37 1994", secs: EXAMPLE_SECS }.test();
+    Test { raw: b"Thu, 01 Jan 1970 00:00:00 GMT", secs: 0 }.test();
+    Test { raw: b"Thu, 29 Feb 2024 12:00:00 GMT", secs: 1_709_208_000 }.test();
+}
+
+#[derive(Debug)]
+struct TestErr {
+    raw: &'static [u8],
+    error: Error,
+}
+impl TestErr {
+    fn test(self) {
+        let error = match HttpDate::parse(self.raw) {
+            Err(error) => error,
+            Ok(date) => panic!("Unexpected `Ok` for {:?} ({date})", String::from_utf8_lossy(self.raw)),
+        };
+        assert_eq!(
+            self.error.to_string(),
+            error.to_string(),
+            "Unexpected error for {:?}",
+            String::from_utf8_lossy(self.raw)
+        );
+    }
+}
+#[test]
+fn test_parse_err() {
+    TestErr { raw: b"Sun, 06 Nov 1994 08:49:37 UTC", error: Error::InvalidFieldValue { offset: 25 } }.test();
+    TestErr { raw: b"Sun, 6 Nov 1994 08:49:37 GMT", error: Error::InvalidFieldValue { offset: 6 } }.test();
+    TestErr { raw: b"Sun, 06 nov 1994 08:49:37 GMT", error: Error::InvalidFieldValue { offset: 8 } }.test();
+    TestErr { raw: b"Sun, 06 Nov 1994 24:00:00 GMT", error: Error::InvalidFieldValue { offset: 17 } }.test();
+    TestErr { raw: b"Sun, 06 Nov 1994 08:49:37 GMT ", error: Error::InvalidFieldValue { offset: 29 } }.test();
+    TestErr { raw: b"Fri, 29 Feb 2023 12:00:00 GMT", error: Error::InvalidFieldValue { offset: 0 } }.test();
+    TestErr { raw: b"Sun, 06 Nov 1969 08:49:37 GMT", error: Error::InvalidFieldValue { offset: 0 } }.test();
+    TestErr { raw: b"0", error: Error::InvalidFieldValue { offset: 0 } }.test();
+}
+
+#[test]
+fn test_format() {
+    // Dates as (seconds since the UNIX epoch, formatted date)
+    let dates = [
+        (0, "Thu, 01 Jan 1970 00:00:00 GMT"),
+        (EXAMPLE_SECS, "Sun, 06 Nov 1994 08:49:37 GMT"),
+        (951_825_600, "Tue, 29 Feb 2000 12:00:00 GMT"),
+        (4_102_444_799, "Thu, 31 Dec 2099 23:59:59 GMT"),
+    ];
+    for (secs, formatted) in dates {
+        let date = HttpDate::from(UNIX_EPOCH + Duration::from_secs(secs));
+        assert_eq!(formatted, date.to_string());
+        assert_eq!(date, HttpDate::parse(formatted.as_bytes()).expect("Invalid date"));
+    }
+
+    // Conversions saturate and truncate the subseconds
+    let before_epoch = HttpDate::from(UNIX_EPOCH - Duration::from_secs(1));
+    assert_eq!("Thu, 01 Jan 1970 00:00:00 GMT", before_epoch.to_string());
+    let far_future = HttpDate::from(UNIX_EPOCH + Duration::from_secs(u32::MAX as u64 * 100));
+    assert_eq!("Fri, 31 Dec 9999 23:59:59 GMT", far_future.to_string());
+    let subsecond = HttpDate::from(UNIX_EPOCH + Duration::from_millis(1999));
+    assert_eq!(UNIX_EPOCH + Duration::from_secs(1), SystemTime::from(subsecond));
+}
+
+#[test]
+fn test_fields() {
+    let raw = b"HTTP/1.1 200 OK\r\nDate: Sun, 06 Nov 1994 08:49:37 GMT\r\nExpires: 0\r\n\r\n";
+    let header = Header::read(&mut helpers::source(raw)).expect("Failed to read header");
+    let date = header.fields().get_date("date").expect("Invalid date").expect("Missing date");
+    assert_eq!(UNIX_EPOCH + Duration::from_secs(EXAMPLE_SECS), SystemTime::from(date));
+    assert_eq!(None, header.fields().get_date("last-modified").expect("Invalid date"));
+    assert!(matches!(header.fields().get_date("expires"), Err(Error::InvalidFieldValue { offset: 0 })));
+}
+
+#[cfg(feature = "convenience")]
+#[test]
+fn test_header() {
+    use http_tiny::convenience::response::ResponseBuilder;
+
+    let header = Header::new_response("200", "OK").set_date(UNIX_EPOCH + Duration::from_secs(EXAMPLE_SECS));
+    let mut serialized = Vec::new();
+    header.write_all(&mut serialized).expect("Failed to write header");
+    assert_eq!(b"HTTP/1.1 200 OK\r\ndate: Sun, 06 Nov 1994 08:49:37 GMT\r\n\r\n", serialized.as_slice());
+}
@@ -0,0 +1,124 @@
+mod helpers;
+
+use http_tiny::{Header, HeaderFields};
+use std::{borrow::Cow, io::Cursor, iter::FromIterator};
+
+struct Test {
+    raw: &'static [u8],
+    name: &'static str,
+    values: &'static [&'static [u8]],
+    combined: Option<&'static [u8]>,
+}
+impl Test {
+    fn test(self) {
+        let header = Header::read(&mut helpers::source(self.raw)).expect("Failed to read header");
+        let values: Vec<_> = header.fields().get_all(self.name).collect();
+        assert_eq!(self.values, values.as_slice());
+        assert_eq!(self.values.first().copied(), header.fields().get(self.name));
+        assert_eq!(self.combined.map(Cow::Borrowed), header.fields().get_combined(self.name));
+    }
+}
+#[test]
+fn test() {
+    let raw = concat!(
+        "HTTP/1.1 200 OK\r\n",
+        "Set-Cookie: a=1\r\n",
+        "Via: 1.1 proxy0\r\n",
+        "Set-Cookie: b=2\r\n",
+        "Via: 1.1 proxy1\r\n",
+        "set-cookie: c=3\r\n",
+        "Server: nginx\r\n",
+        "\r\n"
+    )
+    .as_bytes();
+
+    Test { raw, name: "Set-Cookie", values: &[b"a=1", b"b=2", b"c=3"], combined: Some(b"a=1, b=2, c=3") }.test();
+    Test { raw, name: "via", values: &[b"1.1 proxy0", b"1.1 proxy1"], combined: Some(b"1.1 proxy0, 1.1 proxy1") }
+        .test();
+    Test { raw, name: "SERVER", values: &[b"nginx"], combined: Some(b"nginx") }.test();
+    Test { raw, name: "Warning", values: &[], combined: None }.test();
+}
+
+#[test]
+fn test_modify() {
+    let mut fields = HeaderFields::from_iter([("Set-Cookie", "a=1"), ("Server", "nginx")]);
+    fields.append("set-cookie", "b=2");
+    assert_eq!(3, fields.len());
+    assert_eq!(vec![b"a=1" as &[u8], b"b=2"], fields.get_all("Set-Cookie").collect::<Vec<_>>());
+
+    fields.set("Set-Cookie", "c=3");
+    assert_eq!(vec![b"c=3" as &[u8]], fields.get_all("Set-Cookie").collect::<Vec<_>>());
+
+    assert_eq!(1, fields.remove_all("SET-COOKIE"));
+    assert_eq!(0, fields.remove_all("set-cookie"));
+    assert_eq!(vec![(b"Server" as &[u8], b"nginx" as &[u8])], fields.iter().collect::<Vec<_>>());
+}
+
+#[test]
+fn test_write() {
+    let fields = HeaderFields::from_iter([("set-cookie", "a=1"), ("set-cookie", "b=2")]);
+    let mut serialized = Cursor::new(Vec::new());
+    fields.write_all(&mut serialized).unwrap();
+    assert_eq!(b"set-cookie: a=1\r\nset-cookie: b=2\r\n\r\n", serialized.into_inner().as_slice());
+}
+
+#[test]
+fn test_roundtrip() {
+    let raw = concat!(
+        "HTTP/1.1 200 OK\r\n",
+        "X-Custom-ID: 7\r\n",
+        "content-type: text/plain\r\n",
+        "Set-Cookie: a=1\r\n",
+        "ETag: \"abc\"\r\n",
+        "SET-COOKIE: b=2\r\n",
+        "\r\n"
+    )
+    .as_bytes();
+
+    let header = Header::read(&mut helpers::source(raw)).expect("Failed to read header");
+    assert_eq!(Some(b"7" as &[u8]), header.fields().get("x-custom-id"));
+    assert_eq!(Some(b"text/plain" as &[u8]), header.fields().get("Content-Type"));
+
+    let mut serialized = Cursor::new(Vec::new());
+    header.write_all(&mut serialized).unwrap();
+    assert_eq!(raw, serialized.into_inner().as_slice());
+}
+
+#[test]
+fn test_roundtrip_layout() {
+    let raws: [&[u8]; 5] = [
+        b"GET / HTTP/1.1\r\nHost:a\r\nAccept:  */* \t\r\n\r\n",
+        b"GET / HTTP/1.1\nHost: a\nAccept: */*\r\n\n",
+        b"HTTP/1.1 200\r\nServer:\tnginx\r\n\r\n",
+        b"HTTP/1.1 200 \r\nServer: nginx\r\n\r\n",
+        b"HTTP/1.1 204 No Content\nETag:\r\n\r\n",
+    ];
+    for raw in raws {
+        let header = Header::read(&mut helpers::source(raw)).expect("Failed to read header");
+        let mut serialized = Vec::new();
+        header.write_all(&mut serialized).unwrap();
+        assert_eq!(String::from_utf8_lossy(raw), String::from_utf8_lossy(&serialized));
+
+        let (parsed, len) = Header::parse(raw).expect("Failed to parse header").complete().expect("Incomplete header");
+        let mut serialized = Vec::new();
+        parsed.write_all(&mut serialized).unwrap();
+        assert_eq!((raw.len(), raw), (len, serialized.as_slice()));
+        assert_eq!(header, parsed);
+    }
+
+    // Modified fields are written canonically
+    let raw = b"GET / HTTP/1.1\nHost:a\nAccept:  */*\n\n";
+    let mut header = Header::read(&mut helpers::source(raw)).expect("Failed to read header");
+    header.fields_mut().set("host", "b");
+    let mut serialized = Vec::new();
+    header.write_all(&mut serialized).unwrap();
+    assert_eq!(b"GET / HTTP/1.1\nhost: b\r\nAccept:  */*\n\n" as &[u8], serialized.as_slice());
+}
+
+#[test]
+fn test_set_position() {
+    let mut fields = HeaderFields::from_iter([("Via", "a"), ("Server", "nginx"), ("via", "b"), ("Age", "1")]);
+    fields.set("VIA", "c");
+    let expected: Vec<(&[u8], &[u8])> = vec![(b"VIA", b"c"), (b"Server", b"nginx"), (b"Age", b"1")];
+    assert_eq!(expected, fields.iter().collect::<Vec<_>>());
+}
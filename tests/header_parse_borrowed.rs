@@ -0,0 +1,73 @@
+use http_tiny::{Header, HeaderRef, Status};
+
+struct Test {
+    raw: &'static [u8],
+    field0: &'static [u8],
+    field1: &'static [u8],
+    field2: &'static [u8],
+    fields: &'static [(&'static [u8], &'static [u8])],
+}
+impl Test {
+    fn test(self) {
+        let (header, consumed) = match HeaderRef::parse(self.raw).expect("Failed to parse header") {
+            Status::Complete(header) => header,
+            Status::Partial => panic!("Unexpected partial header: {}", String::from_utf8_lossy(self.raw)),
+        };
+        assert_eq!(self.field0, header.start_line().field0());
+        assert_eq!(self.field1, header.start_line().field1());
+        assert_eq!(self.field2, header.start_line().field2());
+        assert_eq!(self.fields, header.fields().iter().collect::<Vec<_>>().as_slice());
+
+        // Ensure that all slices borrow from the input buffer
+        let range = self.raw.as_ptr_range();
+        for (name, value) in header.fields().iter() {
+            assert!(range.contains(&name.as_ptr()), "Field name is not borrowed from input");
+            assert!(value.is_empty() || range.contains(&value.as_ptr()), "Field value is not borrowed from input");
+        }
+
+        // Compare the owned copy with the regular parser
+        let mut source = self.raw;
+        let expected = Header::read(&mut source).expect("Failed to read header");
+        assert_eq!(expected, header.to_owned());
+        assert_eq!(self.raw.len() - source.len(), consumed);
+    }
+}
+#[test]
+fn test() {
+    Test { raw: b"HEAD / HTTP/1.1\r\n\r\n", field0: b"HEAD", field1: b"/", field2: b"HTTP/1.1", fields: &[] }.test();
+
+    Test {
+        raw: concat!(
+            "HTTP/1.1 404 Not Found\r\n",
+            "Server: nginx\r\n",
+            "Set-Cookie: a=1\r\n",
+            "Set-Cookie: b=2\r\n",
+            "\r\n",
+            "Test\r\nBODY\r\nolope"
+        )
+        .as_bytes(),
+        field0: b"HTTP/1.1",
+        field1: b"404",
+        field2: b"Not Found",
+        fields: &[(b"Server", b"nginx"), (b"Set-Cookie", b"a=1"), (b"Set-Cookie", b"b=2")],
+    }
+    .test();
+}
+
+#[test]
+fn test_fields() {
+    let raw = b"GET / HTTP/1.1\r\nAccept: a\r\nHost: localhost\r\naccept: b\r\n\r\n";
+    let (header, _) = HeaderRef::parse(raw).expect("Failed to parse header").complete().expect("Incomplete header");
+    assert_eq!(Some(b"localhost" as &[u8]), header.fields().get("HOST"));
+    assert_eq!(vec![b"a" as &[u8], b"b"], header.fields().get_all("Accept").collect::<Vec<_>>());
+    assert_eq!(3, header.fields().len());
+}
+
+#[test]
+fn test_partial() {
+    let raw = b"GET / HTTP/1.1\r\nHost: localhost\r\n\r\n";
+    for len in 0..raw.len() {
+        let status = HeaderRef::parse(&raw[..len]).expect("Failed to parse header");
+        assert_eq!(Status::Partial, status, "Unexpected complete status for length {len}");
+    }
+}
@@ -0,0 +1,61 @@
+mod helpers;
+
+use http_tiny::{Error, Header, Limit, ReadOptions};
+use std::io::{self, ErrorKind};
+
+#[derive(Debug)]
+struct TestErr {
+    data: &'static [u8],
+    error: Error,
+}
+impl TestErr {
+    fn test(self) {
+        let options = ReadOptions { max_field_count: 2, ..Default::default() };
+        let error = match Header::read_with(&mut helpers::source(self.data), &options) {
+            Err(error) => error,
+            Ok(header) => panic!("Unexpected `Ok` for header: {} ({:?})", String::from_utf8_lossy(self.data), header),
+        };
+        assert_eq!(
+            self.error.to_string(),
+            error.to_string(),
+            "Unexpected error for header: {}",
+            String::from_utf8_lossy(self.data)
+        );
+    }
+}
+#[test]
+fn test() {
+    TestErr { data: b"", error: Error::Incomplete }.test();
+    TestErr { data: b"GET / HTTP/1.1\r\nHost: localhost\r\n", error: Error::Incomplete }.test();
+    TestErr { data: b"GET /\r\n\r\n", error: Error::InvalidStartLine { offset: 0 } }.test();
+    TestErr { data: b" / HTTP/1.1\r\n\r\n", error: Error::InvalidStartLine { offset: 0 } }.test();
+    TestErr {
+        data: b"GET / HTTP/1.1\r\nHost: localhost\r\nUser-Agent\r\n\r\n",
+        error: Error::InvalidFieldName { offset: 33 },
+    }
+    .test();
+    TestErr { data: b"GET / HTTP/1.1\r\n: localhost\r\n\r\n", error: Error::InvalidFieldName { offset: 16 } }.test();
+    TestErr { data: b"GET / HTTP/1.1\r\nHost: local\rhost\r\n\r\n", error: Error::InvalidFieldValue { offset: 16 } }
+        .test();
+    TestErr {
+        data: b"GET / HTTP/1.1\r\nA: 1\r\nB: 2\r\nC: 3\r\n\r\n",
+        error: Error::LimitExceeded { limit: Limit::FieldCount, offset: 28 },
+    }
+    .test();
+}
+
+#[test]
+fn test_io() {
+    // Convert into an I/O error and back
+    let error = io::Error::from(Error::InvalidFieldName { offset: 7 });
+    assert_eq!(ErrorKind::InvalidData, error.kind());
+    assert!(matches!(Error::from(error), Error::InvalidFieldName { offset: 7 }));
+
+    // Wrap and unwrap a plain I/O error
+    let error = Error::from(io::Error::from(ErrorKind::BrokenPipe));
+    assert!(matches!(&error, Error::Io(error) if error.kind() == ErrorKind::BrokenPipe));
+    assert_eq!(ErrorKind::BrokenPipe, io::Error::from(error).kind());
+
+    // Test the kind of incomplete errors
+    assert_eq!(ErrorKind::UnexpectedEof, Error::Incomplete.kind());
+}
@@ -0,0 +1,68 @@
+mod helpers;
+
+use http_tiny::{Error, Header, HeaderParser, Limit, ReadOptions, Status};
+
+const RAW: &[u8] = concat!(
+    "HTTP/1.1 200 OK\r\n",
+    "Server: nginx\r\n",
+    "Date: Sun, 26 May 2019 22:02:50 GMT\r\n",
+    "Content-Type: text/html; charset=UTF-8\r\n",
+    "Content-Length: 17\r\n",
+    "\r\n",
+    "Test\r\nBODY\r\nolope"
+)
+.as_bytes();
+const HEADER_LEN: usize = RAW.len() - 17;
+
+struct Test {
+    chunk_size: usize,
+}
+impl Test {
+    fn test(self) {
+        let expected = Header::read(&mut helpers::source(RAW)).expect("Failed to read header");
+
+        // Feed the data chunk by chunk
+        let (mut parser, mut buf) = (HeaderParser::new(), Vec::new());
+        for chunk in RAW.chunks(self.chunk_size) {
+            buf.extend_from_slice(chunk);
+            match parser.parse(&buf).expect("Failed to parse header") {
+                Status::Complete((header, consumed)) => {
+                    assert_eq!(expected, header);
+                    assert_eq!(HEADER_LEN, consumed);
+                    assert_eq!(b"Test\r\nBODY\r\nolope", &RAW[consumed..]);
+                    return;
+                }
+                Status::Partial => assert!(buf.len() < HEADER_LEN, "Unexpected partial status"),
+            }
+        }
+        panic!("Incomplete header for chunk size {}", self.chunk_size);
+    }
+}
+#[test]
+fn test() {
+    for chunk_size in [1, 2, 3, 7, 16, 64, RAW.len()] {
+        Test { chunk_size }.test();
+    }
+}
+
+#[test]
+fn test_partial() {
+    for len in 0..HEADER_LEN {
+        let status = Header::parse(&RAW[..len]).expect("Failed to parse header");
+        assert_eq!(Status::Partial, status, "Unexpected complete status for length {len}");
+    }
+    let status = Header::parse(&RAW[..HEADER_LEN]).expect("Failed to parse header");
+    assert!(status.is_complete());
+}
+
+#[test]
+fn test_limit() {
+    // The limit must be detected even if the header is incomplete
+    let options = ReadOptions { max_start_line: 8, ..Default::default() };
+    let result = Header::parse_with(&RAW[..12], &options);
+    assert!(matches!(result, Err(Error::LimitExceeded { limit: Limit::StartLine, offset: 0 })));
+
+    let options = ReadOptions { max_header_size: 40, ..Default::default() };
+    let result = Header::parse_with(&RAW[..48], &options);
+    assert!(matches!(result, Err(Error::LimitExceeded { limit: Limit::HeaderSize, offset: 32 })));
+}
@@ -1,25 +1,25 @@
 mod helpers;
 
-use http_tiny::Header;
-use std::{borrow::Cow, collections::BTreeMap, io::ErrorKind, ops::Deref};
+use http_tiny::{Header, HeaderFields};
+use std::{io::ErrorKind, iter::FromIterator};
 
 struct Test {
     raw: &'static [u8],
     method: &'static [u8],
     target: &'static [u8],
-    fields: BTreeMap<Cow<'static, [u8]>, Cow<'static, [u8]>>,
+    fields: HeaderFields,
 }
 impl Test {
     fn test(self) {
         let header = Header::read(&mut helpers::source(self.raw)).expect("Failed to read header");
         assert_eq!(self.method, header.start_line().field0());
         assert_eq!(self.target, header.start_line().field1());
-        assert_eq!(&self.fields, header.fields().deref());
+        assert_eq!(&self.fields, header.fields());
     }
 }
 #[test]
 fn test() {
-    Test { raw: b"HEAD / HTTP/1.1\r\n\r\n", method: b"HEAD", target: b"/", fields: BTreeMap::new() }.test();
+    Test { raw: b"HEAD / HTTP/1.1\r\n\r\n", method: b"HEAD", target: b"/", fields: HeaderFields::new() }.test();
 
     Test {
         raw: concat!(
@@ -32,7 +32,7 @@ fn test() {
         .as_bytes(),
         method: b"POST",
         target: b"/upl%C3%B6ad/form.php",
-        fields: helpers::map([("host", "www.heise.de"), ("user-agent", "http-tiny/0.3.0")]),
+        fields: HeaderFields::from_iter([("Host", "www.heise.de"), ("User-Agent", "http-tiny/0.3.0")]),
     }
     .test();
 }
@@ -59,12 +59,12 @@ impl TestErr {
 #[test]
 fn test_err() {
     TestErr { data: b"HEAD / HTTP/1.1\r\n", error: ErrorKind::UnexpectedEof }.test();
-    TestErr { data: b"\r\n\r\n", error: ErrorKind::UnexpectedEof }.test();
-    TestErr { data: b"HEAD / \r\n\r\n", error: ErrorKind::UnexpectedEof }.test();
+    TestErr { data: b"\r\n\r\n", error: ErrorKind::InvalidData }.test();
+    TestErr { data: b"HEAD / \r\n\r\n", error: ErrorKind::InvalidData }.test();
 
     TestErr {
         data: concat!("HEAD / HTTP/1.1\r\n", "Host: www.heise.de\r\n", "User-Agent \r\n", "\r\n").as_bytes(),
-        error: ErrorKind::UnexpectedEof,
+        error: ErrorKind::InvalidData,
     }
     .test();
 }
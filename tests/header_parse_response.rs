@@ -1,25 +1,27 @@
 mod helpers;
 
-use http_tiny::Header;
-use std::{borrow::Cow, collections::BTreeMap, io::ErrorKind, ops::Deref};
+use http_tiny::{Header, HeaderFields};
+use std::{io::ErrorKind, iter::FromIterator};
 
 struct Test {
     raw: &'static [u8],
     status: u16,
     reason: &'static [u8],
-    fields: BTreeMap<Cow<'static, [u8]>, Cow<'static, [u8]>>,
+    fields: HeaderFields,
 }
 impl Test {
     fn test(self) {
         let header = Header::read(&mut helpers::source(self.raw)).expect("Failed to read header");
         assert_eq!(self.status.to_string().as_bytes(), header.start_line().field1());
         assert_eq!(self.reason, header.start_line().field2());
-        assert_eq!(&self.fields, header.fields().deref());
+        assert_eq!(&self.fields, header.fields());
     }
 }
 #[test]
 fn test() {
-    Test { raw: b"HTTP/1.1 200 OK\r\n\r\n", status: 200, reason: b"OK", fields: BTreeMap::new() }.test();
+    Test { raw: b"HTTP/1.1 200 OK\r\n\r\n", status: 200, reason: b"OK", fields: HeaderFields::new() }.test();
+    Test { raw: b"HTTP/1.1 204 \r\n\r\n", status: 204, reason: b"", fields: HeaderFields::new() }.test();
+    Test { raw: b"HTTP/1.1 200\r\n\r\n", status: 200, reason: b"", fields: HeaderFields::new() }.test();
 
     Test {
         raw: concat!(
@@ -44,21 +46,21 @@ fn test() {
         .as_bytes(),
         status: 200,
         reason: b"OK",
-        fields: helpers::map([
-            ("server", "nginx"),
-            ("date", "Sun, 26 May 2019 22:02:50 GMT"),
-            ("content-type", "text/html; charset=UTF-8"),
-            ("last-modified", "Sun, 26 May 2019 22:02:50 GMT"),
-            ("cache-control", "public, max-age=30"),
-            ("age", "25"),
-            ("strict-transport-security", "max-age=15768000"),
-            ("x-frame-options", "DENY"),
-            ("x-xss-protection", "1; mode=block"),
-            ("x-content-type-options", "nosniff"),
-            ("vary", "Accept-Encoding,X-Export-Format,X-Export-Agent"),
-            ("accept-ranges", "bytes"),
-            ("content-length", "417889"),
-            ("connection", "keep-alive"),
+        fields: HeaderFields::from_iter([
+            ("Server", "nginx"),
+            ("Date", "Sun, 26 May 2019 22:02:50 GMT"),
+            ("Content-Type", "text/html; charset=UTF-8"),
+            ("Last-Modified", "Sun, 26 May 2019 22:02:50 GMT"),
+            ("Cache-Control", "public, max-age=30"),
+            ("Age", "25"),
+            ("Strict-Transport-Security", "max-age=15768000"),
+            ("X-Frame-Options", "DENY"),
+            ("X-XSS-Protection", "1; mode=block"),
+            ("X-Content-Type-Options", "nosniff"),
+            ("Vary", "Accept-Encoding,X-Export-Format,X-Export-Agent"),
+            ("Accept-Ranges", "bytes"),
+            ("Content-Length", "417889"),
+            ("Connection", "keep-alive"),
         ]),
     }
     .test();
@@ -85,12 +87,13 @@ impl TestErr {
 #[test]
 fn test_err() {
     TestErr { data: b"HTTP/1.1 200 OK\r\n", error: ErrorKind::UnexpectedEof }.test();
-    TestErr { data: b"\r\n\r\n", error: ErrorKind::UnexpectedEof }.test();
-    TestErr { data: b"HTTP/1.1 200 \r\n\r\n", error: ErrorKind::UnexpectedEof }.test();
+    TestErr { data: b"\r\n\r\n", error: ErrorKind::InvalidData }.test();
+    TestErr { data: b"HTTP/1.1\r\n\r\n", error: ErrorKind::InvalidData }.test();
+    TestErr { data: b"HTTP/1.1  OK\r\n\r\n", error: ErrorKind::InvalidData }.test();
 
     TestErr {
         data: concat!("HTTP/1.1 200 OK\r\n", "Server: nginx\r\n", "Date \r\n", "\r\n").as_bytes(),
-        error: ErrorKind::UnexpectedEof,
+        error: ErrorKind::InvalidData,
     }
     .test();
 }
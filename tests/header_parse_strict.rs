@@ -0,0 +1,188 @@
+mod helpers;
+
+use http_tiny::{Error, Header, HeaderFields, HeaderRef, HeaderStartLine, ParseMode, ReadOptions};
+use std::iter::FromIterator;
+
+struct Test {
+    mode: ParseMode,
+    data: &'static [u8],
+    start_line: HeaderStartLine,
+    fields: HeaderFields,
+}
+impl Test {
+    fn test(self) {
+        let options = ReadOptions { mode: self.mode, ..Default::default() };
+        let data = String::from_utf8_lossy(self.data);
+
+        // Read the header from a stream, parse it from a buffer and parse the borrowed header from a buffer
+        let read = Header::read_with(&mut helpers::source(self.data), &options).expect("Failed to read header");
+        let parsed = Header::parse_with(self.data, &options).expect("Failed to parse header");
+        let borrowed = HeaderRef::parse_with(self.data, &options).expect("Failed to parse borrowed header");
+        let parsed = parsed.complete().expect("Partial header").0;
+        let borrowed = borrowed.complete().expect("Partial header").0.to_owned();
+        for header in [read, parsed, borrowed] {
+            assert_eq!(
+                &self.start_line,
+                header.start_line(),
+                "Unexpected start line for {:?} header: {data}",
+                self.mode
+            );
+            assert_eq!(&self.fields, header.fields(), "Unexpected fields for {:?} header: {data}", self.mode);
+        }
+    }
+}
+
+#[derive(Debug)]
+struct TestErr {
+    mode: ParseMode,
+    data: &'static [u8],
+    error: Error,
+}
+impl TestErr {
+    fn test(self) {
+        let options = ReadOptions { mode: self.mode, ..Default::default() };
+        let data = String::from_utf8_lossy(self.data);
+
+        // Read the header from a stream, parse it from a buffer and parse the borrowed header from a buffer
+        let read = Header::read_with(&mut helpers::source(self.data), &options).map(|_| ());
+        let parsed = Header::parse_with(self.data, &options).map(|_| ());
+        let borrowed = HeaderRef::parse_with(self.data, &options).map(|_| ());
+        for result in [read, parsed, borrowed] {
+            let error = result.expect_err("Unexpected `Ok`");
+            assert_eq!(
+                self.error.to_string(),
+                error.to_string(),
+                "Unexpected error for {:?} header: {data}",
+                self.mode
+            );
+        }
+    }
+}
+
+#[test]
+fn test_strict() {
+    use ParseMode::Strict;
+
+    Test {
+        mode: Strict,
+        data: b"GET / HTTP/1.1\r\nHost: localhost\r\n\r\n",
+        start_line: HeaderStartLine::new_request("GET", "/"),
+        fields: HeaderFields::from_iter([("Host", "localhost")]),
+    }
+    .test();
+    Test {
+        mode: Strict,
+        data: b"HTTP/1.1 404 Not Found\r\nX-Tab:\tvalue\t\r\n\r\n",
+        start_line: HeaderStartLine::new_response(404, "Not Found"),
+        fields: HeaderFields::from_iter([("X-Tab", "value")]),
+    }
+    .test();
+    Test {
+        mode: Strict,
+        data: b"HTTP/1.1 204 \r\n\r\n",
+        start_line: HeaderStartLine::new_response(204, ""),
+        fields: HeaderFields::new(),
+    }
+    .test();
+}
+
+#[test]
+fn test_strict_err() {
+    use ParseMode::Strict;
+
+    TestErr { mode: Strict, data: b"HTTP/1.1 200\r\n\r\n", error: Error::InvalidStartLine { offset: 12 } }.test();
+    TestErr {
+        mode: Strict,
+        data: b"GET / HTTP/1.1\nHost: localhost\r\n\r\n",
+        error: Error::InvalidStartLine { offset: 0 },
+    }
+    .test();
+    TestErr {
+        mode: Strict,
+        data: b"GET / HTTP/1.1\r\nHost: localhost\n\r\n",
+        error: Error::InvalidFieldValue { offset: 16 },
+    }
+    .test();
+    TestErr {
+        mode: Strict,
+        data: b"GET / HTTP/1.1\r\nHost : localhost\r\n\r\n",
+        error: Error::InvalidFieldName { offset: 20 },
+    }
+    .test();
+    TestErr {
+        mode: Strict,
+        data: b"GET / HTTP/1.1\r\nX-Long: a\r\n b\r\n\r\n",
+        error: Error::InvalidFieldName { offset: 27 },
+    }
+    .test();
+    TestErr {
+        mode: Strict,
+        data: b"GET / HTTP/1.1\r\nX(Y): value\r\n\r\n",
+        error: Error::InvalidFieldName { offset: 17 },
+    }
+    .test();
+    TestErr { mode: Strict, data: b"GE@T / HTTP/1.1\r\n\r\n", error: Error::InvalidStartLine { offset: 2 } }.test();
+    TestErr { mode: Strict, data: b"GET / HTTP/1.10\r\n\r\n", error: Error::InvalidStartLine { offset: 14 } }.test();
+    TestErr { mode: Strict, data: b"GET / FTP/1.1\r\n\r\n", error: Error::InvalidStartLine { offset: 6 } }.test();
+    TestErr { mode: Strict, data: b"HTTP/1.1 20 OK\r\n\r\n", error: Error::InvalidStartLine { offset: 11 } }.test();
+    TestErr { mode: Strict, data: b"HTTP/1.1 2000 OK\r\n\r\n", error: Error::InvalidStartLine { offset: 12 } }.test();
+}
+
+#[test]
+fn test_lenient() {
+    use ParseMode::Lenient;
+
+    Test {
+        mode: Lenient,
+        data: b"GET / HTTP/1.1\nHost: localhost\n\n",
+        start_line: HeaderStartLine::new_request("GET", "/"),
+        fields: HeaderFields::from_iter([("Host", "localhost")]),
+    }
+    .test();
+    Test {
+        mode: Lenient,
+        data: b"GET / HTTP/1.1\r\nHost:\t localhost \t\r\n\n",
+        start_line: HeaderStartLine::new_request("GET", "/"),
+        fields: HeaderFields::from_iter([("Host", "localhost")]),
+    }
+    .test();
+    Test {
+        mode: Lenient,
+        data: b"HTTP/1.1 200\r\n\r\n",
+        start_line: HeaderStartLine::new_response(200, ""),
+        fields: HeaderFields::new(),
+    }
+    .test();
+}
+
+#[test]
+fn test_lenient_err() {
+    use ParseMode::Lenient;
+
+    TestErr {
+        mode: Lenient,
+        data: b"GET / HTTP/1.1\r\nHost \t: localhost\r\n\r\n",
+        error: Error::InvalidFieldName { offset: 20 },
+    }
+    .test();
+    TestErr {
+        mode: Lenient,
+        data: b"GET / HTTP/1.1\r\nHo st: a\r\n\r\n",
+        error: Error::InvalidFieldName { offset: 18 },
+    }
+    .test();
+    TestErr {
+        mode: Lenient,
+        data: b"GET / HTTP/1.1\r\nX-Long: a\r\n b\r\n\r\n",
+        error: Error::InvalidFieldName { offset: 27 },
+    }
+    .test();
+}
+
+#[test]
+fn test_default_rejects_whitespace_before_colon() {
+    // A field name with trailing whitespace must not be interpreted as `Transfer-Encoding` (see RFC 9112, section 5.1)
+    let raw = b"POST / HTTP/1.1\r\nTransfer-Encoding : chunked\r\n\r\n";
+    let error = Header::read(&mut helpers::source(raw)).expect_err("Unexpected `Ok`");
+    assert_eq!(Error::InvalidFieldName { offset: 34 }.to_string(), error.to_string());
+}
@@ -0,0 +1,36 @@
+use http_tiny::Header;
+use std::io::{BufReader, Read};
+
+const RAW: &[u8] = concat!(
+    "POST /upl%C3%B6ad/form.php HTTP/1.1\r\n",
+    "Host: www.heise.de\r\n",
+    "User-Agent: http-tiny/2.0.0\r\n",
+    "Content-Length: 17\r\n",
+    "\r\n",
+    "Test\r\nBODY\r\nolope"
+)
+.as_bytes();
+
+struct Test {
+    capacity: usize,
+}
+impl Test {
+    fn test(self) {
+        // Read the header with a small buffer so that the delimiters span across chunk boundaries
+        let mut source = BufReader::with_capacity(self.capacity, RAW);
+        let header = Header::read(&mut source).expect("Failed to read header");
+        assert_eq!(b"POST", header.start_line().field0());
+        assert_eq!(Some(b"17" as &[u8]), header.fields().get("content-length"));
+
+        // Ensure that the body has not been consumed
+        let mut body = Vec::new();
+        source.read_to_end(&mut body).expect("Failed to read body");
+        assert_eq!(b"Test\r\nBODY\r\nolope", body.as_slice(), "Invalid body for capacity {}", self.capacity);
+    }
+}
+#[test]
+fn test() {
+    for capacity in 1..=RAW.len() {
+        Test { capacity }.test();
+    }
+}
@@ -0,0 +1,61 @@
+mod helpers;
+
+use http_tiny::{Error, Header, Limit, ReadOptions};
+
+const RAW: &[u8] = concat!(
+    "GET /index.html HTTP/1.1\r\n",
+    "Host: www.heise.de\r\n",
+    "User-Agent: http-tiny/2.0.0\r\n",
+    "Accept: */*\r\n",
+    "\r\n"
+)
+.as_bytes();
+
+struct Test {
+    options: ReadOptions,
+    limit: Option<Limit>,
+}
+impl Test {
+    fn test(self) {
+        let result = Header::read_with(&mut helpers::source(RAW), &self.options);
+        let limit = match result {
+            Ok(_) => None,
+            Err(Error::LimitExceeded { limit, .. }) => Some(limit),
+            Err(error) => panic!("Unexpected error \"{error}\" for options: {:?}", self.options),
+        };
+        assert_eq!(self.limit, limit, "Unexpected result for options: {:?}", self.options);
+    }
+}
+#[test]
+fn test() {
+    Test { options: ReadOptions::default(), limit: None }.test();
+    Test { options: ReadOptions::unlimited(), limit: None }.test();
+
+    // Exact limits
+    let exact = ReadOptions {
+        max_start_line: 24,
+        max_field_line: 27,
+        max_field_count: 3,
+        max_header_size: 90,
+        ..Default::default()
+    };
+    Test { options: exact, limit: None }.test();
+
+    // Exceeded limits
+    Test { options: ReadOptions { max_start_line: 23, ..exact }, limit: Some(Limit::StartLine) }.test();
+    Test { options: ReadOptions { max_field_line: 26, ..exact }, limit: Some(Limit::FieldLine) }.test();
+    Test { options: ReadOptions { max_field_count: 2, ..exact }, limit: Some(Limit::FieldCount) }.test();
+    Test { options: ReadOptions { max_header_size: 89, ..exact }, limit: Some(Limit::HeaderSize) }.test();
+    Test { options: ReadOptions { max_header_size: 20, ..exact }, limit: Some(Limit::HeaderSize) }.test();
+}
+
+#[test]
+fn test_read_unlimited() {
+    // `Header::read` imposes no limits, unlike the default options
+    let fields = "X-Field: value\r\n".repeat(200);
+    let raw = ["GET / HTTP/1.1\r\n", &fields, "\r\n"].concat();
+    let header = Header::read(&mut helpers::source(raw.as_bytes())).expect("Failed to read header");
+    assert_eq!(200, header.fields().len());
+    let result = Header::read_with(&mut helpers::source(raw.as_bytes()), &ReadOptions::default());
+    assert!(matches!(result, Err(Error::LimitExceeded { limit: Limit::FieldCount, .. })), "{result:?}");
+}
@@ -0,0 +1,64 @@
+use http_tiny::{Header, HeaderFields, HeaderRef, HeaderStartLine, HeaderStartLineRef};
+use std::borrow::Cow;
+
+struct Test {
+    raw: &'static [u8],
+    expected: HeaderStartLineRef<'static>,
+}
+impl Test {
+    fn test(self) {
+        // Parse the start line
+        let (header, _) =
+            HeaderRef::parse(self.raw).expect("Failed to parse header").complete().expect("Partial header");
+        assert_eq!(&self.expected, header.start_line());
+        assert_eq!(header.is_request(), self.expected.is_request());
+        assert_eq!(header.is_response(), self.expected.is_response());
+
+        // Compare the typed accessors of the owned copy
+        let owned = header.to_owned();
+        assert_eq!(owned.is_request(), self.expected.is_request());
+        assert_eq!(owned.start_line().method(), self.expected.method());
+        assert_eq!(owned.start_line().target(), self.expected.target());
+        assert_eq!(owned.start_line().version(), self.expected.version());
+        assert_eq!(owned.start_line().status(), self.expected.status());
+        assert_eq!(owned.start_line().reason(), self.expected.reason());
+
+        // Ensure that the positional accessors remain consistent
+        let fields = [self.expected.field0(), self.expected.field1(), self.expected.field2()];
+        assert_eq!(self.raw[..self.raw.len() - 4], fields.join(&b' ')[..]);
+    }
+}
+#[test]
+fn test() {
+    Test {
+        raw: b"GET /index.html HTTP/1.1\r\n\r\n",
+        expected: HeaderStartLineRef::Request { method: b"GET", target: b"/index.html", version: b"HTTP/1.1" },
+    }
+    .test();
+    Test {
+        raw: b"HTTP/1.0 404 Not Found\r\n\r\n",
+        expected: HeaderStartLineRef::Response { version: b"HTTP/1.0", status: b"404", reason: b"Not Found" },
+    }
+    .test();
+}
+
+#[test]
+fn test_construct() {
+    let request = Header::new(HeaderStartLine::new_request("POST", "/upload"), HeaderFields::new());
+    assert!(request.is_request() && !request.is_response());
+    assert_eq!(Some(b"POST" as &[u8]), request.start_line().method());
+    assert_eq!(None, request.start_line().status());
+
+    let mut response = Header::new(HeaderStartLine::new_response(200, "OK"), HeaderFields::new());
+    assert!(response.is_response() && !response.is_request());
+    assert_eq!(b"HTTP/1.1", response.start_line().version());
+    assert_eq!(None, response.start_line().target());
+
+    // Modify the start line via pattern matching
+    if let HeaderStartLine::Response { status, reason, .. } = response.start_line_mut() {
+        (*status, *reason) = (Cow::Borrowed(b"404" as &[u8]), Cow::Borrowed(b"Not Found" as &[u8]));
+    }
+    let mut serialized = Vec::new();
+    response.write_all(&mut serialized).expect("Failed to write header");
+    assert_eq!(b"HTTP/1.1 404 Not Found\r\n\r\n", serialized.as_slice());
+}
@@ -0,0 +1,101 @@
+use http_tiny::{Error, HeaderFields, HeaderStartLine};
+
+struct Test {
+    name: &'static [u8],
+    value: &'static [u8],
+}
+impl Test {
+    fn test(self) {
+        let mut set = HeaderFields::new();
+        set.try_set(self.name, self.value).expect("Failed to set field");
+        let mut appended = HeaderFields::new();
+        appended.try_append(self.name, self.value).expect("Failed to append field");
+
+        let expected = vec![(self.name, self.value)];
+        assert_eq!(expected, set.iter().collect::<Vec<_>>());
+        assert_eq!(expected, appended.iter().collect::<Vec<_>>());
+    }
+}
+#[test]
+fn test_field() {
+    Test { name: b"Content-Type", value: b"text/plain" }.test();
+    Test { name: b"X-Empty", value: b"" }.test();
+    Test { name: b"X-Tab", value: b"a\tb c" }.test();
+    Test { name: b"X-Obs-Text", value: b"caf\xc3\xa9" }.test();
+}
+
+#[derive(Debug)]
+struct TestErr {
+    name: &'static [u8],
+    value: &'static [u8],
+    error: Error,
+}
+impl TestErr {
+    fn test(self) {
+        let mut set = HeaderFields::new();
+        let set_error = set.try_set(self.name, self.value).expect_err("Unexpected `Ok` for set");
+        let mut appended = HeaderFields::new();
+        let append_error = appended.try_append(self.name, self.value).expect_err("Unexpected `Ok` for append");
+        for error in [set_error, append_error] {
+            assert_eq!(
+                self.error.to_string(),
+                error.to_string(),
+                "Unexpected error for {:?}: {:?}",
+                self.name,
+                self.value
+            );
+        }
+
+        // Invalid fields must not be inserted
+        assert!(set.is_empty() && appended.is_empty());
+    }
+}
+#[test]
+fn test_field_err() {
+    TestErr { name: b"", value: b"value", error: Error::InvalidFieldName { offset: 0 } }.test();
+    TestErr { name: b"Set Cookie", value: b"value", error: Error::InvalidFieldName { offset: 3 } }.test();
+    TestErr { name: b"Host:", value: b"value", error: Error::InvalidFieldName { offset: 4 } }.test();
+    TestErr { name: b"Location", value: b"/\r\nSet-Cookie: injected=1", error: Error::InvalidFieldValue { offset: 1 } }
+        .test();
+    TestErr { name: b"Location", value: b"/\nX: 1", error: Error::InvalidFieldValue { offset: 1 } }.test();
+    TestErr { name: b"X-Null", value: b"a\0b", error: Error::InvalidFieldValue { offset: 1 } }.test();
+    TestErr { name: b"X-Space", value: b" value", error: Error::InvalidFieldValue { offset: 0 } }.test();
+    TestErr { name: b"X-Space", value: b"value ", error: Error::InvalidFieldValue { offset: 5 } }.test();
+}
+
+#[test]
+fn test_request() {
+    let expected = HeaderStartLine::new_request("GET", "/index.html?a=b");
+    assert_eq!(expected, HeaderStartLine::try_new_request("GET", "/index.html?a=b").expect("Invalid request"));
+    let expected = HeaderStartLine::new_request("OPTIONS", "*");
+    assert_eq!(expected, HeaderStartLine::try_new_request("OPTIONS", "*").expect("Invalid request"));
+    assert!(matches!(HeaderStartLine::try_new_request("", "/"), Err(Error::InvalidStartLine { offset: 0 })));
+    assert!(matches!(HeaderStartLine::try_new_request("G T", "/"), Err(Error::InvalidStartLine { offset: 1 })));
+    assert!(matches!(HeaderStartLine::try_new_request("GET", ""), Err(Error::InvalidStartLine { offset: 4 })));
+    assert!(matches!(
+        HeaderStartLine::try_new_request("GET", "/ HTTP/1.1\r\nHost: evil"),
+        Err(Error::InvalidStartLine { offset: 5 })
+    ));
+}
+
+#[test]
+fn test_response() {
+    let expected = HeaderStartLine::new_response(200, "OK");
+    assert_eq!(expected, HeaderStartLine::try_new_response(200, "OK").expect("Invalid response"));
+    let expected = HeaderStartLine::new_response("404", "Not Found");
+    assert_eq!(expected, HeaderStartLine::try_new_response("404", "Not Found").expect("Invalid response"));
+    let expected = HeaderStartLine::new_response("204", "");
+    assert_eq!(expected, HeaderStartLine::try_new_response("204", "").expect("Invalid response"));
+    assert!(matches!(HeaderStartLine::try_new_response("20", "OK"), Err(Error::InvalidStartLine { offset: 11 })));
+    assert!(matches!(HeaderStartLine::try_new_response("2000", "OK"), Err(Error::InvalidStartLine { offset: 12 })));
+    assert!(matches!(HeaderStartLine::try_new_response("2x0", "OK"), Err(Error::InvalidStartLine { offset: 10 })));
+    assert!(matches!(HeaderStartLine::try_new_response(200, "OK\r\n"), Err(Error::InvalidStartLine { offset: 15 })));
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "invalid HTTP header field")]
+fn test_unchecked() {
+    let mut fields = HeaderFields::new();
+    fields.set("Location", "/\r\nSet-Cookie: injected=1");
+}
@@ -27,7 +27,7 @@ fn test() {
             HeaderStartLine::new_request("GET", "/"),
             HeaderFields::from_iter([("Host", "www.heise.de")]),
         ),
-        raw: concat!("GET / HTTP/1.1\r\n", "host: www.heise.de\r\n", "\r\n").as_bytes(),
+        raw: concat!("GET / HTTP/1.1\r\n", "Host: www.heise.de\r\n", "\r\n").as_bytes(),
     }
     .test();
 }
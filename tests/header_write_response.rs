@@ -21,13 +21,18 @@ fn test() {
         raw: b"HTTP/1.1 200 OK\r\n\r\n",
     }
     .test();
+    Test {
+        header: Header::new(HeaderStartLine::new_response(204, ""), HeaderFields::new()),
+        raw: b"HTTP/1.1 204 \r\n\r\n",
+    }
+    .test();
 
     Test {
         header: Header::new(
             HeaderStartLine::new_response(200, "OK"),
             HeaderFields::from_iter([("Date", "Sun, 26 May 2019 22:02:50 GMT")]),
         ),
-        raw: concat!("HTTP/1.1 200 OK\r\n", "date: Sun, 26 May 2019 22:02:50 GMT\r\n", "\r\n").as_bytes(),
+        raw: concat!("HTTP/1.1 200 OK\r\n", "Date: Sun, 26 May 2019 22:02:50 GMT\r\n", "\r\n").as_bytes(),
     }
     .test();
 }
@@ -0,0 +1,120 @@
+mod helpers;
+
+use http_tiny::{typed::ContentType, Error, Header, MediaType};
+
+struct Test {
+    raw: &'static [u8],
+    type_: &'static [u8],
+    subtype: &'static [u8],
+    parameters: &'static [(&'static [u8], &'static [u8])],
+}
+impl Test {
+    fn test(self) {
+        let media_type = MediaType::parse(self.raw).expect("Failed to parse media type");
+        assert_eq!(
+            (self.type_, self.subtype, self.parameters.to_vec()),
+            (media_type.type_(), media_type.subtype(), media_type.parameters().collect::<Vec<_>>()),
+            "Unexpected media type for {:?}",
+            String::from_utf8_lossy(self.raw)
+        );
+
+        // Ensure that the serialized media type roundtrips
+        assert_eq!(media_type, MediaType::parse(&media_type.to_bytes()).expect("Failed to reparse media type"));
+    }
+}
+#[test]
+fn test_parse() {
+    Test {
+        raw: b"Text/HTML; Charset=\"UTF-8\"",
+        type_: b"text",
+        subtype: b"html",
+        parameters: &[(b"charset", b"utf-8")],
+    }
+    .test();
+    Test {
+        raw: b"multipart/form-data ;boundary=\"a \\\"quoted\\\" string\";;  x=1",
+        type_: b"multipart",
+        subtype: b"form-data",
+        parameters: &[(b"boundary", b"a \"quoted\" string"), (b"x", b"1")],
+    }
+    .test();
+    Test { raw: b"*/*", type_: b"*", subtype: b"*", parameters: &[] }.test();
+}
+
+#[derive(Debug)]
+struct TestErr {
+    raw: &'static [u8],
+    error: Error,
+}
+impl TestErr {
+    fn test(self) {
+        let error = match MediaType::parse(self.raw) {
+            Err(error) => error,
+            Ok(media_type) => panic!("Unexpected `Ok` for {:?} ({media_type})", String::from_utf8_lossy(self.raw)),
+        };
+        assert_eq!(
+            self.error.to_string(),
+            error.to_string(),
+            "Unexpected error for {:?}",
+            String::from_utf8_lossy(self.raw)
+        );
+    }
+}
+#[test]
+fn test_parse_err() {
+    TestErr { raw: b"text", error: Error::InvalidFieldValue { offset: 4 } }.test();
+    TestErr { raw: b"/html", error: Error::InvalidFieldValue { offset: 0 } }.test();
+    TestErr { raw: b"text/", error: Error::InvalidFieldValue { offset: 5 } }.test();
+    TestErr { raw: b"text/h(tml", error: Error::InvalidFieldValue { offset: 6 } }.test();
+    TestErr { raw: b"text/html charset=utf-8", error: Error::InvalidFieldValue { offset: 10 } }.test();
+    TestErr { raw: b"text/html; charset", error: Error::InvalidFieldValue { offset: 18 } }.test();
+    TestErr { raw: b"text/html; charset=", error: Error::InvalidFieldValue { offset: 19 } }.test();
+    TestErr { raw: b"text/html; charset=\"utf-8", error: Error::InvalidFieldValue { offset: 25 } }.test();
+    TestErr { raw: b"text/html; charset=utf 8", error: Error::InvalidFieldValue { offset: 23 } }.test();
+}
+
+#[test]
+fn test_compare_and_serialize() {
+    // Media types compare case-insensitively except for parameter values
+    let media_type = MediaType::parse(b"TEXT/plain; CHARSET=UTF-8").expect("Invalid media type");
+    assert_eq!(MediaType::parse(b"text/Plain;charset=utf-8").expect("Invalid media type"), media_type);
+    let quoted = MediaType::parse(b"text/plain; format=\"Flowed\"").expect("Invalid media type");
+    assert_ne!(MediaType::parse(b"text/plain; format=flowed").expect("Invalid media type"), quoted);
+    assert_eq!("text/plain; charset=utf-8", media_type.to_string());
+
+    // Values are quoted if necessary
+    let built = MediaType::new(b"multipart", b"Mixed")
+        .and_then(|media_type| media_type.with_parameter(b"boundary", b"simple boundary"))
+        .and_then(|media_type| media_type.with_parameter(b"note", b"a\\b\"c"))
+        .and_then(|media_type| media_type.with_parameter(b"empty", b""))
+        .expect("Invalid media type");
+    assert_eq!(
+        b"multipart/mixed; boundary=\"simple boundary\"; note=\"a\\\\b\\\"c\"; empty=\"\"",
+        built.to_bytes().as_slice()
+    );
+    assert!(matches!(MediaType::new(b"text", b"a b"), Err(Error::InvalidFieldValue { offset: 1 })));
+    assert!(matches!(
+        MediaType::new(b"text", b"plain").and_then(|media_type| media_type.with_parameter(b"x", b"\r\n")),
+        Err(Error::InvalidFieldValue { offset: 0 })
+    ));
+}
+
+#[test]
+fn test_content_type() {
+    let raw = b"HTTP/1.1 200 OK\r\nContent-Type: application/json; charset=UTF-8\r\n\r\n";
+    let header = Header::read(&mut helpers::source(raw)).expect("Failed to read header");
+    let content_type = header.fields().typed_get::<ContentType>().expect("Invalid field").expect("Missing field");
+    let media_type = content_type.media_type().expect("Invalid media type");
+    assert_eq!((b"application/json" as &[u8], Some(b"utf-8" as &[u8])), (media_type.essence(), media_type.charset()));
+    assert_eq!(b"application/json; charset=utf-8", ContentType::from(media_type).as_bytes());
+}
+
+#[cfg(feature = "convenience")]
+#[test]
+fn test_header() {
+    use http_tiny::convenience::{constants::CONTENTTYPE_TEXTHTML_UTF8, response::ResponseBuilder};
+
+    let media_type = MediaType::parse(CONTENTTYPE_TEXTHTML_UTF8.as_bytes()).expect("Invalid media type");
+    let header = Header::new_response("200", "OK").set_content_type(media_type);
+    assert_eq!(Some(CONTENTTYPE_TEXTHTML_UTF8.as_bytes()), header.fields().get("content-type"));
+}
@@ -0,0 +1,147 @@
+mod helpers;
+
+use http_tiny::{Body, Header, HeaderFields, HeaderStartLine, Message, ReadOptions};
+use std::{
+    io::{ErrorKind, Read},
+    iter::FromIterator,
+};
+
+struct Test {
+    raw: &'static [u8],
+    request_method: Option<&'static [u8]>,
+    body: &'static [u8],
+    serialized: &'static [u8],
+}
+impl Test {
+    fn test(self) {
+        // Read the message and its body
+        let source = helpers::source([self.raw, b"NEXT"].concat());
+        let mut message = Message::read_from_with(source, &ReadOptions::default(), self.request_method)
+            .expect("Failed to read message");
+        let mut body = Vec::new();
+        message.body_mut().read_to_end(&mut body).expect("Failed to read body");
+        assert_eq!(self.body, body.as_slice());
+
+        // Ensure that the next message has not been consumed unless the body is delimited by the connection
+        let (header, body) = message.into_parts();
+        let mut trailing = Vec::new();
+        body.into_inner().read_to_end(&mut trailing).expect("Failed to read trailing data");
+        match self.body.ends_with(b"NEXT") {
+            true => assert!(trailing.is_empty()),
+            false => assert_eq!(b"NEXT", trailing.as_slice()),
+        }
+
+        // Serialize the message with the decoded body
+        let mut serialized = Vec::new();
+        Message::new(header, self.body).write_to(&mut serialized).expect("Failed to write message");
+        assert_eq!(self.serialized, serialized.as_slice());
+    }
+}
+#[test]
+fn test() {
+    Test {
+        raw: b"POST / HTTP/1.1\r\nContent-Length: 4\r\n\r\nTest",
+        request_method: None,
+        body: b"Test",
+        serialized: b"POST / HTTP/1.1\r\nContent-Length: 4\r\n\r\nTest",
+    }
+    .test();
+    Test {
+        raw: b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n4\r\nWiki\r\n5\r\npedia\r\n0\r\n\r\n",
+        request_method: Some(b"GET"),
+        body: b"Wikipedia",
+        serialized: b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n9\r\nWikipedia\r\n0\r\n\r\n",
+    }
+    .test();
+    Test {
+        raw: b"HTTP/1.1 200 OK\r\n\r\nTest",
+        request_method: Some(b"GET"),
+        body: b"TestNEXT",
+        serialized: b"HTTP/1.1 200 OK\r\n\r\nTestNEXT",
+    }
+    .test();
+    Test {
+        raw: b"HTTP/1.1 204 No Content\r\n\r\n",
+        request_method: Some(b"GET"),
+        body: b"",
+        serialized: b"HTTP/1.1 204 No Content\r\n\r\n",
+    }
+    .test();
+    Test {
+        raw: b"GET / HTTP/1.1\r\nHost: localhost\r\n\r\n",
+        request_method: None,
+        body: b"",
+        serialized: b"GET / HTTP/1.1\r\nHost: localhost\r\n\r\n",
+    }
+    .test();
+}
+
+#[test]
+fn test_framing() {
+    // Read a message with conflicting framing
+    let source = helpers::source(b"POST / HTTP/1.1\r\nContent-Length: 4\r\nTransfer-Encoding: chunked\r\n\r\n");
+    let error = Message::read_from(source).expect_err("Unexpected success for conflicting framing");
+    assert_eq!(ErrorKind::InvalidData, error.kind());
+
+    // Write a body that does not match the declared length
+    let header =
+        Header::new(HeaderStartLine::new_request("POST", "/"), HeaderFields::from_iter([("Content-Length", "5")]));
+    let mut message = Message::new(header, b"Test" as &[u8]);
+    let error = message.write_to(&mut Vec::new()).expect_err("Unexpected success for short body");
+    assert_eq!(ErrorKind::InvalidInput, error.kind());
+
+    // Write a message with conflicting framing without writing the header
+    let fields = HeaderFields::from_iter([("Content-Length", "4"), ("Transfer-Encoding", "chunked")]);
+    let mut message = Message::new(Header::new(HeaderStartLine::new_request("POST", "/"), fields), b"Test" as &[u8]);
+    let mut output = Vec::new();
+    let error = message.write_to(&mut output).expect_err("Unexpected success for conflicting framing");
+    assert_eq!((ErrorKind::InvalidData, 0), (error.kind(), output.len()));
+
+    // Write a body without framing fields
+    let header = Header::new(HeaderStartLine::new_request("POST", "/"), HeaderFields::new());
+    let mut message = Message::new(header.clone(), b"Test" as &[u8]);
+    let mut output = Vec::new();
+    let error = message.write_to(&mut output).expect_err("Unexpected success for unframed body");
+    assert_eq!((ErrorKind::InvalidInput, 0), (error.kind(), output.len()));
+    let mut output = Vec::new();
+    Message::new(header, b"" as &[u8]).write_to(&mut output).expect("Failed to write message without body");
+    assert_eq!(b"POST / HTTP/1.1\r\n\r\n", output.as_slice());
+
+    // Forward responses without body that retain their Content-Length
+    let raw = b"HTTP/1.1 200 OK\r\nContent-Length: 42\r\n\r\n";
+    let header = Header::read(&mut helpers::source(raw)).expect("Failed to read header");
+    let mut message = Message::new(header.clone(), b"" as &[u8]);
+    let error = message.write_to(&mut Vec::new()).expect_err("Unexpected success for missing body");
+    assert_eq!(ErrorKind::InvalidInput, error.kind());
+    let mut output = Vec::new();
+    message.write_to_with(&mut output, Some(b"HEAD")).expect("Failed to write response to HEAD");
+    assert_eq!(raw, output.as_slice());
+    let error = Message::new(header, b"Test" as &[u8])
+        .write_to_with(&mut Vec::new(), Some(b"HEAD"))
+        .expect_err("Unexpected success for body in response to HEAD");
+    assert_eq!(ErrorKind::InvalidInput, error.kind());
+
+    let raw = b"HTTP/1.1 304 Not Modified\r\nContent-Length: 42\r\n\r\n";
+    let header = Header::read(&mut helpers::source(raw)).expect("Failed to read header");
+    let mut output = Vec::new();
+    Message::new(header, b"" as &[u8]).write_to(&mut output).expect("Failed to write 304 response");
+    assert_eq!(raw, output.as_slice());
+}
+
+#[test]
+fn test_keep_alive() {
+    let raw = b"POST /a HTTP/1.1\r\nContent-Length: 1\r\n\r\naPOST /b HTTP/1.1\r\nContent-Length: 1\r\n\r\nb";
+    let mut source = helpers::source(raw);
+    for (target, expected) in [(b"/a", "a"), (b"/b", "b")] {
+        let message = Message::read_from(source).expect("Failed to read message");
+        assert_eq!(Some(target as &[u8]), message.header().start_line().target());
+
+        // Read the body and reuse the source for the next message
+        let (_, mut body) = message.into_parts();
+        let mut content = String::new();
+        body.read_to_string(&mut content).expect("Failed to read body");
+        assert_eq!(expected, content);
+        assert!(matches!(body, Body::ContentLength(_)));
+        source = body.into_inner();
+    }
+}
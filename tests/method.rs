@@ -0,0 +1,91 @@
+mod helpers;
+
+use http_tiny::{Error, Method};
+use std::borrow::Cow;
+
+struct Test {
+    raw: &'static [u8],
+    method: Method,
+}
+impl Test {
+    fn test(self) {
+        let method = Method::parse(self.raw).expect("Failed to parse method");
+        assert_eq!(self.method, method, "Unexpected method for {:?}", String::from_utf8_lossy(self.raw));
+
+        // Ensure that the method token roundtrips
+        assert_eq!(self.raw, method.as_bytes());
+    }
+}
+#[test]
+fn test_parse() {
+    Test { raw: b"GET", method: Method::Get }.test();
+    Test { raw: b"PATCH", method: Method::Patch }.test();
+    Test { raw: b"PROPFIND", method: Method::Extension(Cow::Borrowed(b"PROPFIND")) }.test();
+    Test { raw: b"get", method: Method::Extension(Cow::Borrowed(b"get")) }.test();
+}
+
+#[derive(Debug)]
+struct TestErr {
+    raw: &'static [u8],
+    error: Error,
+}
+impl TestErr {
+    fn test(self) {
+        let error = match Method::parse(self.raw) {
+            Err(error) => error,
+            Ok(method) => panic!("Unexpected `Ok` for {:?} ({method:?})", String::from_utf8_lossy(self.raw)),
+        };
+        assert_eq!(
+            self.error.to_string(),
+            error.to_string(),
+            "Unexpected error for {:?}",
+            String::from_utf8_lossy(self.raw)
+        );
+    }
+}
+#[test]
+fn test_parse_err() {
+    TestErr { raw: b"", error: Error::InvalidStartLine { offset: 0 } }.test();
+    TestErr { raw: b"GE T", error: Error::InvalidStartLine { offset: 2 } }.test();
+}
+
+#[test]
+fn test_properties() {
+    // Methods as (method, is_safe, is_idempotent, is_cacheable)
+    let methods = [
+        (Method::Get, true, true, true),
+        (Method::Head, true, true, true),
+        (Method::Post, false, false, true),
+        (Method::Put, false, true, false),
+        (Method::Delete, false, true, false),
+        (Method::Connect, false, false, false),
+        (Method::Options, true, true, false),
+        (Method::Trace, true, true, false),
+        (Method::Patch, false, false, false),
+        (Method::Extension(Cow::Borrowed(b"PROPFIND")), false, false, false),
+    ];
+    for (method, is_safe, is_idempotent, is_cacheable) in methods {
+        assert_eq!(is_safe, method.is_safe(), "Unexpected safety for {method}");
+        assert_eq!(is_idempotent, method.is_idempotent(), "Unexpected idempotency for {method}");
+        assert_eq!(is_cacheable, method.is_cacheable(), "Unexpected cacheability for {method}");
+    }
+}
+
+#[cfg(feature = "convenience")]
+#[test]
+fn test_header() {
+    use http_tiny::{
+        convenience::request::{RequestBuilder, RequestHeader},
+        Header,
+    };
+
+    // Build a request from a method
+    let header = Header::new_request(Method::Delete, "/items/7");
+    let mut serialized = Vec::new();
+    header.write_all(&mut serialized).expect("Failed to write header");
+    assert_eq!(b"DELETE /items/7 HTTP/1.1\r\n\r\n", serialized.as_slice());
+
+    // Parse the method of a request header
+    let header = Header::read(&mut helpers::source(serialized)).expect("Failed to read header");
+    assert_eq!(Method::Delete, header.method_typed().expect("Invalid method"));
+}
@@ -0,0 +1,200 @@
+mod helpers;
+
+use http_tiny::{
+    Accept, AcceptCharset, AcceptEncoding, AcceptLanguage, Error, Header, MediaType, Negotiate, QValue, TypedHeader,
+};
+use std::{fmt::Debug, marker::PhantomData};
+
+struct Test<T> {
+    values: &'static [&'static [u8]],
+    encoded: &'static [u8],
+    field: PhantomData<T>,
+}
+impl<T> Test<T>
+where
+    T: TypedHeader + Debug + PartialEq,
+{
+    fn test(self) {
+        let field = T::decode(self.values).expect("Failed to decode field");
+        let encoded = field.encode();
+        assert_eq!(
+            String::from_utf8_lossy(self.encoded),
+            String::from_utf8_lossy(&encoded),
+            "Unexpected field for {} {:?}",
+            T::NAME,
+            self.values
+        );
+
+        // Ensure that the encoded field decodes to the same value
+        assert_eq!(field, T::decode(&[&encoded]).expect("Failed to decode encoded field"));
+    }
+}
+
+#[derive(Debug)]
+struct TestErr<T> {
+    values: &'static [&'static [u8]],
+    error: Error,
+    field: PhantomData<T>,
+}
+impl<T> TestErr<T>
+where
+    T: TypedHeader + Debug,
+{
+    fn test(self) {
+        let error = match T::decode(self.values) {
+            Err(error) => error,
+            Ok(field) => panic!("Unexpected `Ok` for {} {:?} ({field:?})", T::NAME, self.values),
+        };
+        assert_eq!(self.error.to_string(), error.to_string(), "Unexpected error for {} {:?}", T::NAME, self.values);
+    }
+}
+
+/// Parses a media type
+fn media_type(raw: &str) -> MediaType {
+    MediaType::parse(raw.as_bytes()).expect("Invalid media type")
+}
+
+#[test]
+fn test_qvalue() {
+    let valid: [(&[u8], u16); 7] =
+        [(b"0", 0), (b"1", 1000), (b"0.", 0), (b"0.5", 500), (b"0.05", 50), (b"0.123", 123), (b"1.000", 1000)];
+    for (raw, millis) in valid {
+        assert_eq!(QValue::from_millis(millis), QValue::parse(raw).ok(), "{:?}", String::from_utf8_lossy(raw));
+    }
+    let invalid: [(&[u8], usize); 6] = [(b"", 0), (b"2", 0), (b"0,5", 1), (b"1.001", 4), (b"0.1234", 5), (b".5", 0)];
+    for (raw, offset) in invalid {
+        assert!(
+            matches!(QValue::parse(raw), Err(Error::InvalidFieldValue { offset: actual }) if actual == offset),
+            "{:?}",
+            String::from_utf8_lossy(raw)
+        );
+    }
+    assert_eq!(None, QValue::from_millis(1001));
+    assert_eq!(
+        ["0", "0.05", "0.5", "0.123", "1"],
+        [0, 50, 500, 123, 1000].map(|millis| QValue::from_millis(millis).expect("Invalid quality").to_string())
+    );
+}
+
+#[test]
+fn test_decode() {
+    Test::<Accept> {
+        values: &[b"text/html;level=1;q=0.7;ext=\"a,b\", */*;q=0.1", b"application/*"],
+        encoded: b"text/html; level=1;q=0.7, */*;q=0.1, application/*",
+        field: PhantomData,
+    }
+    .test();
+    Test::<Accept> { values: &[b""], encoded: b"", field: PhantomData }.test();
+    Test::<AcceptLanguage> {
+        values: &[b"de-CH, de;q=0.9, *;Q=0.1"],
+        encoded: b"de-CH, de;q=0.9, *;q=0.1",
+        field: PhantomData,
+    }
+    .test();
+    Test::<AcceptEncoding> {
+        values: &[b"gzip;q=1.0, identity; q=0.5", b"*;q=0"],
+        encoded: b"gzip, identity;q=0.5, *;q=0",
+        field: PhantomData,
+    }
+    .test();
+    Test::<AcceptCharset> {
+        values: &[b"iso-8859-5, unicode-1-1;q=0.8"],
+        encoded: b"iso-8859-5, unicode-1-1;q=0.8",
+        field: PhantomData,
+    }
+    .test();
+}
+
+#[test]
+fn test_decode_err() {
+    TestErr::<Accept> {
+        values: &[b"text/html, */html"],
+        error: Error::InvalidFieldValue { offset: 0 },
+        field: PhantomData,
+    }
+    .test();
+    TestErr::<Accept> {
+        values: &[b"text/html, text/plain;q=2"],
+        error: Error::InvalidFieldValue { offset: 21 },
+        field: PhantomData,
+    }
+    .test();
+    TestErr::<AcceptLanguage> {
+        values: &[b"en, de_DE"],
+        error: Error::InvalidFieldValue { offset: 6 },
+        field: PhantomData,
+    }
+    .test();
+    TestErr::<AcceptLanguage> {
+        values: &[b"en-abcdefghi"],
+        error: Error::InvalidFieldValue { offset: 3 },
+        field: PhantomData,
+    }
+    .test();
+    TestErr::<AcceptEncoding> {
+        values: &[b"gzip;level=1"],
+        error: Error::InvalidFieldValue { offset: 4 },
+        field: PhantomData,
+    }
+    .test();
+}
+
+#[test]
+fn test_quality() {
+    // The most specific media range determines the quality (see RFC 9110, section 12.5.1)
+    let accept = Accept::decode(&[b"text/*;q=0.3, text/plain;q=0.7, text/plain;format=flowed, */*;q=0.5"])
+        .expect("Invalid accept");
+    let expected = [("text/plain; format=flowed", 1000), ("text/plain", 700), ("text/html", 300), ("image/jpeg", 500)];
+    for (raw, millis) in expected {
+        assert_eq!(millis, accept.quality(&media_type(raw)).as_millis(), "{raw}");
+    }
+
+    // Language ranges match prefixes at subtag boundaries
+    let language = AcceptLanguage::decode(&[b"de-CH, de;q=0.5, en;q=0"]).expect("Invalid accept language");
+    let expected: [(&[u8], u16); 5] = [(b"de-ch", 1000), (b"de-DE", 500), (b"de", 500), (b"dev", 0), (b"en-US", 0)];
+    for (tag, millis) in expected {
+        assert_eq!(millis, language.quality(tag).as_millis(), "{:?}", String::from_utf8_lossy(tag));
+    }
+
+    // The identity coding is acceptable unless excluded explicitly
+    let encoding = AcceptEncoding::decode(&[b"GZIP"]).expect("Invalid accept encoding");
+    assert_eq!(
+        [1000, 1000, 0],
+        [b"gzip" as &[u8], b"identity", b"br"].map(|coding| encoding.quality(coding).as_millis())
+    );
+    let encoding = AcceptEncoding::decode(&[b"br, *;q=0"]).expect("Invalid accept encoding");
+    assert_eq!([1000, 0], [b"br" as &[u8], b"identity"].map(|coding| encoding.quality(coding).as_millis()));
+    let encoding = AcceptEncoding::decode(&[b""]).expect("Invalid accept encoding");
+    assert_eq!([0, 1000], [b"gzip" as &[u8], b"identity"].map(|coding| encoding.quality(coding).as_millis()));
+}
+
+#[test]
+fn test_negotiate() {
+    let available = [media_type("application/json"), media_type("application/cbor"), media_type("text/html")];
+
+    // Select the preferred representation of the client
+    let raw = b"GET / HTTP/1.1\r\nAccept: text/html, application/*;q=0.9\r\nAccept: application/cbor\r\n\r\n";
+    let header = Header::read(&mut helpers::source(raw)).expect("Failed to read header");
+    assert_eq!(Some(&available[1]), header.negotiate::<Accept, _>(&available).expect("Invalid accept"));
+
+    // Ties are broken by the order of the available representations
+    let raw = b"GET / HTTP/1.1\r\nAccept: text/html, application/*\r\n\r\n";
+    let header = Header::read(&mut helpers::source(raw)).expect("Failed to read header");
+    assert_eq!(Some(&available[0]), header.negotiate::<Accept, _>(&available).expect("Invalid accept"));
+
+    // Nothing acceptable
+    let raw = b"GET / HTTP/1.1\r\nAccept: image/*, application/json;q=0\r\nAccept-Language: fr\r\n\r\n";
+    let header = Header::read(&mut helpers::source(raw)).expect("Failed to read header");
+    assert_eq!(None, header.negotiate::<Accept, _>(&available).expect("Invalid accept"));
+    let languages: [&[u8]; 2] = [b"en", b"de"];
+    assert_eq!(None, header.negotiate::<AcceptLanguage, _>(languages).expect("Invalid accept language"));
+
+    // Absent fields accept everything and invalid fields are reported
+    let raw = b"GET / HTTP/1.1\r\nAccept-Charset: utf-8;q=x\r\n\r\n";
+    let header = Header::read(&mut helpers::source(raw)).expect("Failed to read header");
+    assert_eq!(Some(&available[0]), header.negotiate::<Accept, _>(&available).expect("Invalid accept"));
+    let codings: [&[u8]; 2] = [b"gzip", b"identity"];
+    assert_eq!(Some(b"gzip" as &[u8]), header.negotiate::<AcceptEncoding, _>(codings).expect("Invalid encoding"));
+    let charsets: [&[u8]; 1] = [b"utf-8"];
+    assert!(matches!(header.negotiate::<AcceptCharset, _>(charsets), Err(Error::InvalidFieldValue { offset: 5 })));
+}
@@ -0,0 +1,142 @@
+mod helpers;
+
+use http_tiny::{Error, HeaderStartLine, Method, RequestTarget};
+use std::borrow::Cow;
+
+struct Test {
+    method: Method,
+    raw: &'static [u8],
+    target: RequestTarget<'static>,
+}
+impl Test {
+    fn test(self) {
+        let target = RequestTarget::parse(self.raw, &self.method).expect("Failed to parse target");
+        assert_eq!(
+            self.target,
+            target,
+            "Unexpected target for {} {:?}",
+            self.method,
+            String::from_utf8_lossy(self.raw)
+        );
+    }
+}
+#[test]
+fn test_parse() {
+    Test {
+        method: Method::Get,
+        raw: b"/search?q=tiny",
+        target: RequestTarget::Origin { path: b"/search", query: Some(b"q=tiny") },
+    }
+    .test();
+    Test { method: Method::Get, raw: b"/", target: RequestTarget::Origin { path: b"/", query: None } }.test();
+    Test {
+        method: Method::Post,
+        raw: b"http://example.com:8080/form?",
+        target: RequestTarget::Absolute {
+            scheme: b"http",
+            authority: b"example.com:8080",
+            path: b"/form",
+            query: Some(b""),
+        },
+    }
+    .test();
+    Test {
+        method: Method::Get,
+        raw: b"https://example.com",
+        target: RequestTarget::Absolute { scheme: b"https", authority: b"example.com", path: b"", query: None },
+    }
+    .test();
+    Test { method: Method::Connect, raw: b"[::1]:443", target: RequestTarget::Authority(b"[::1]:443") }.test();
+    Test { method: Method::Options, raw: b"*", target: RequestTarget::Asterisk }.test();
+}
+
+#[derive(Debug)]
+struct TestErr {
+    method: Method,
+    raw: &'static [u8],
+    error: Error,
+}
+impl TestErr {
+    fn test(self) {
+        let error = match RequestTarget::parse(self.raw, &self.method) {
+            Err(error) => error,
+            Ok(target) => {
+                panic!("Unexpected `Ok` for {} {:?} ({target:?})", self.method, String::from_utf8_lossy(self.raw))
+            }
+        };
+        assert_eq!(
+            self.error.to_string(),
+            error.to_string(),
+            "Unexpected error for {} {:?}",
+            self.method,
+            String::from_utf8_lossy(self.raw)
+        );
+    }
+}
+#[test]
+fn test_parse_err() {
+    // Forms that are invalid for the method
+    TestErr { method: Method::Get, raw: b"*", error: Error::InvalidStartLine { offset: 0 } }.test();
+    TestErr { method: Method::Connect, raw: b"/index.html", error: Error::InvalidStartLine { offset: 0 } }.test();
+    TestErr { method: Method::Get, raw: b"example.com:443", error: Error::InvalidStartLine { offset: 11 } }.test();
+
+    // Malformed targets
+    TestErr { method: Method::Get, raw: b"/page#section", error: Error::InvalidStartLine { offset: 5 } }.test();
+    TestErr { method: Method::Get, raw: b"http://user@example.com/", error: Error::InvalidStartLine { offset: 11 } }
+        .test();
+    TestErr { method: Method::Get, raw: b"http:///path", error: Error::InvalidStartLine { offset: 7 } }.test();
+    TestErr { method: Method::Connect, raw: b"example.com", error: Error::InvalidStartLine { offset: 11 } }.test();
+    TestErr { method: Method::Connect, raw: b"example.com:https", error: Error::InvalidStartLine { offset: 12 } }
+        .test();
+    TestErr { method: Method::Get, raw: b"", error: Error::InvalidStartLine { offset: 0 } }.test();
+}
+
+#[test]
+fn test_effective_uri() {
+    let origin = RequestTarget::parse(b"/a?b", &Method::Get).expect("Invalid target");
+    assert_eq!(Some(b"https://example.com/a?b".to_vec()), origin.effective_uri(b"https", Some(b"example.com")));
+    assert_eq!(None, origin.effective_uri(b"http", Some(b"")));
+    assert_eq!(None, origin.effective_uri(b"http", None));
+
+    // The absolute-form takes precedence over the scheme and host
+    let absolute = RequestTarget::parse(b"http://proxy.test/x", &Method::Get).expect("Invalid target");
+    assert_eq!(Some(b"http://proxy.test/x".to_vec()), absolute.effective_uri(b"https", Some(b"example.com")));
+
+    // Authority- and asterisk-form targets have an empty path
+    let authority = RequestTarget::parse(b"example.com:443", &Method::Connect).expect("Invalid target");
+    assert_eq!(Some(b"http://example.com:443".to_vec()), authority.effective_uri(b"http", None));
+    let asterisk = RequestTarget::parse(b"*", &Method::Options).expect("Invalid target");
+    assert_eq!(Some(b"http://example.com".to_vec()), asterisk.effective_uri(b"http", Some(b"example.com")));
+}
+
+#[test]
+fn test_start_line() {
+    let start_line = HeaderStartLine::new_request("OPTIONS", "*");
+    assert_eq!(RequestTarget::Asterisk, start_line.target_typed().expect("Invalid target"));
+
+    // Status lines have no target
+    let start_line = HeaderStartLine::new_response("200", "OK");
+    assert!(matches!(start_line.target_typed(), Err(Error::InvalidStartLine { offset: 0 })));
+
+    // Mutate the target and reparse it
+    let mut start_line = HeaderStartLine::new_request("GET", "/");
+    *start_line.field1_mut() = Cow::Borrowed(b"/docs?page=2");
+    let target = start_line.target_typed().expect("Invalid target");
+    assert_eq!((b"/docs" as &[u8], Some(b"page=2" as &[u8])), (target.path(), target.query()));
+    assert_eq!((None, None), (target.scheme(), target.authority()));
+}
+
+#[cfg(feature = "convenience")]
+#[test]
+fn test_header() {
+    use http_tiny::{convenience::request::RequestHeader, Header};
+
+    let raw = b"GET /index.html HTTP/1.1\r\nHost: example.com:8080\r\n\r\n";
+    let header = Header::read(&mut helpers::source(raw)).expect("Failed to read header");
+    let uri = header.effective_uri(b"http").expect("Invalid target");
+    assert_eq!(Some(b"http://example.com:8080/index.html".to_vec()), uri);
+
+    // Requests without host cannot be resolved
+    let header = Header::read(&mut helpers::source(b"GET / HTTP/1.0\r\n\r\n")).expect("Failed to read header");
+    assert_eq!(None, header.effective_uri(b"http").expect("Invalid target"));
+}
@@ -0,0 +1,106 @@
+mod helpers;
+
+use http_tiny::{
+    rules::{self, ListElements, Parameters},
+    Error, Header, HeaderRef,
+};
+use std::borrow::Cow;
+
+struct Test {
+    raw: &'static [u8],
+    expected: &'static [&'static [u8]],
+}
+impl Test {
+    fn test(self) {
+        let elements: Vec<_> = ListElements::new(self.raw).collect();
+        assert_eq!(
+            self.expected,
+            elements.as_slice(),
+            "Unexpected elements for {:?}",
+            String::from_utf8_lossy(self.raw)
+        );
+    }
+}
+#[test]
+fn test_list() {
+    Test { raw: b"gzip, deflate", expected: &[b"gzip", b"deflate"] }.test();
+    Test { raw: b" , ,a,\t,b ,", expected: &[b"a", b"b"] }.test();
+    Test { raw: b"", expected: &[] }.test();
+    Test { raw: b"foo;bar=\"a, b\", baz", expected: &[b"foo;bar=\"a, b\"", b"baz"] }.test();
+    Test { raw: b"\"a\\\", b\", c", expected: &[b"\"a\\\", b\"", b"c"] }.test();
+    Test {
+        raw: b"1.0 fred, 1.1 p.example.net (Apache, \\) \"x)",
+        expected: &[b"1.0 fred", b"1.1 p.example.net (Apache, \\) \"x)"],
+    }
+    .test();
+    Test { raw: b"a (nested (comment, x), y), b", expected: &[b"a (nested (comment, x), y)", b"b"] }.test();
+    Test { raw: b"\"unterminated, x", expected: &[b"\"unterminated, x"] }.test();
+
+    // Offsets point at the start of the trimmed elements
+    let offsets: Vec<_> = ListElements::new(b" a, \"b,c\" ,d").with_offsets().collect();
+    assert_eq!(vec![(1, b"a" as &[u8]), (4, b"\"b,c\""), (11, b"d")], offsets);
+}
+
+#[test]
+fn test_values() {
+    assert_eq!(b"no-cache" as &[u8], rules::parse_token(b"no-cache, x").expect("Invalid token"));
+    assert!(matches!(rules::parse_token(b"\"quoted\""), Err(Error::InvalidFieldValue { offset: 0 })));
+
+    let (value, len) = rules::parse_quoted_string(b"\"a\\\"b\" rest").expect("Invalid quoted string");
+    assert_eq!((b"a\"b" as &[u8], 6), (value.as_slice(), len));
+    assert!(matches!(rules::parse_quoted_string(b"\"abc"), Err(Error::InvalidFieldValue { offset: 4 })));
+    assert!(matches!(rules::parse_quoted_string(b"\"a\rb\""), Err(Error::InvalidFieldValue { offset: 2 })));
+    assert!(matches!(rules::parse_token_or_quoted_string(b"abc;"), Ok((Cow::Borrowed(b"abc"), 3))));
+    assert!(matches!(rules::parse_token_or_quoted_string(b"\"a b\""), Ok((Cow::Owned(value), 5)) if value == b"a b"));
+
+    assert_eq!(
+        b"compatible; (nested) \\)" as &[u8],
+        rules::parse_comment(b"(compatible; (nested) \\)) x").expect("Invalid")
+    );
+    assert!(matches!(rules::parse_comment(b"no comment"), Err(Error::InvalidFieldValue { offset: 0 })));
+    assert!(matches!(rules::parse_comment(b"(open (nested)"), Err(Error::InvalidFieldValue { offset: 14 })));
+    assert!(matches!(rules::parse_comment(b"(a\x7fb)"), Err(Error::InvalidFieldValue { offset: 2 })));
+
+    assert!(matches!(rules::parse_directive(b"no-store"), Ok((b"no-store", None))));
+    assert!(matches!(rules::parse_directive(b"max-age=60"), Ok((b"max-age", Some(Cow::Borrowed(b"60"))))));
+    assert!(matches!(
+        rules::parse_directive(b"private=\"set-cookie, x\""),
+        Ok((b"private", Some(Cow::Owned(value)))) if value == b"set-cookie, x"
+    ));
+    assert!(matches!(rules::parse_directive(b"max-age = 60"), Err(Error::InvalidFieldValue { offset: 7 })));
+    assert!(matches!(rules::parse_directive(b"max-age=\"60\"x"), Err(Error::InvalidFieldValue { offset: 12 })));
+    assert!(matches!(rules::parse_directive(b"max-age="), Err(Error::InvalidFieldValue { offset: 8 })));
+}
+
+#[test]
+fn test_parameters() {
+    let parameters: Result<Vec<_>, _> = Parameters::new(b" ;a=1;; B=\"x;y\" ;c=\"\"").collect();
+    let expected: [(&[u8], &[u8]); 3] = [(b"a", b"1"), (b"B", b"x;y"), (b"c", b"")];
+    assert_eq!(
+        expected.as_slice(),
+        parameters.expect("Invalid parameters").iter().map(|(n, v)| (*n, &v[..])).collect::<Vec<_>>()
+    );
+    assert_eq!(0, Parameters::new(b"").count());
+
+    // The iterator ends after the first error
+    let mut parameters = Parameters::new(b";a=1 b=2;c=3");
+    assert!(matches!(parameters.next(), Some(Ok((b"a", _)))));
+    assert!(matches!(parameters.next(), Some(Err(Error::InvalidFieldValue { offset: 5 }))));
+    assert!(parameters.next().is_none());
+    assert!(matches!(Parameters::new(b";=1").next(), Some(Err(Error::InvalidFieldValue { offset: 1 }))));
+    assert!(matches!(Parameters::new(b";a=\"1").next(), Some(Err(Error::InvalidFieldValue { offset: 5 }))));
+}
+
+#[test]
+fn test_fields() {
+    let raw =
+        b"HTTP/1.1 200 OK\r\nVary: Accept-Encoding, \r\nCache-Control: private=\"a, b\"\r\nVary: ,User-Agent\r\n\r\n";
+    let header = Header::read(&mut helpers::source(raw)).expect("Failed to read header");
+    let expected: [&[u8]; 2] = [b"Accept-Encoding", b"User-Agent"];
+    assert!(header.fields().get_list("vary").eq(expected));
+    assert!(header.fields().get_list("cache-control").eq([b"private=\"a, b\"" as &[u8]]));
+    assert_eq!(0, header.fields().get_list("allow").count());
+
+    let (header, _) = HeaderRef::parse(raw).expect("Failed to parse header").complete().expect("Incomplete header");
+    assert!(header.fields().get_list("VARY").eq(expected));
+}
@@ -0,0 +1,83 @@
+mod helpers;
+
+use http_tiny::{Error, StatusCode};
+
+struct Test {
+    raw: &'static [u8],
+    expected: StatusCode,
+}
+impl Test {
+    fn test(self) {
+        let status = StatusCode::parse(self.raw).expect("Failed to parse status code");
+        assert_eq!(self.expected, status, "Unexpected status code for {:?}", String::from_utf8_lossy(self.raw));
+    }
+}
+#[test]
+fn test_parse() {
+    Test { raw: b"200", expected: StatusCode::OK }.test();
+    Test { raw: b"599", expected: StatusCode::new(599).expect("Invalid status code") }.test();
+}
+
+#[derive(Debug)]
+struct TestErr {
+    raw: &'static [u8],
+    error: Error,
+}
+impl TestErr {
+    fn test(self) {
+        let error = match StatusCode::parse(self.raw) {
+            Err(error) => error,
+            Ok(status) => panic!("Unexpected `Ok` for {:?} ({status:?})", String::from_utf8_lossy(self.raw)),
+        };
+        assert_eq!(
+            self.error.to_string(),
+            error.to_string(),
+            "Unexpected error for {:?}",
+            String::from_utf8_lossy(self.raw)
+        );
+    }
+}
+#[test]
+fn test_parse_err() {
+    TestErr { raw: b"20", error: Error::InvalidStartLine { offset: 2 } }.test();
+    TestErr { raw: b"2x0", error: Error::InvalidStartLine { offset: 1 } }.test();
+    TestErr { raw: b"099", error: Error::InvalidStartLine { offset: 0 } }.test();
+    TestErr { raw: b"600", error: Error::InvalidStartLine { offset: 0 } }.test();
+}
+
+#[test]
+fn test_properties() {
+    assert_eq!(Some("Not Found"), StatusCode::NOT_FOUND.canonical_reason());
+    assert_eq!(Some("Content Too Large"), StatusCode::CONTENT_TOO_LARGE.canonical_reason());
+    assert_eq!(None, StatusCode::new(299).and_then(|status| status.canonical_reason()));
+    assert_eq!(None, StatusCode::new(600));
+
+    // Test the classes
+    assert!(StatusCode::EARLY_HINTS.is_informational());
+    assert!(StatusCode::NO_CONTENT.is_success());
+    assert!(StatusCode::PERMANENT_REDIRECT.is_redirection());
+    assert!(StatusCode::TOO_MANY_REQUESTS.is_client_error());
+    assert!(StatusCode::BAD_GATEWAY.is_server_error());
+    assert!(!StatusCode::OK.is_client_error() && !StatusCode::OK.is_server_error());
+}
+
+#[cfg(feature = "convenience")]
+#[test]
+fn test_header() {
+    use http_tiny::{
+        convenience::response::{ResponseBuilder, ResponseHeader},
+        Header,
+    };
+
+    // Build a response from a status code
+    let status = StatusCode::SERVICE_UNAVAILABLE;
+    let header = Header::new_response(status, status.canonical_reason().unwrap_or_default());
+    let mut serialized = Vec::new();
+    header.write_all(&mut serialized).expect("Failed to write header");
+    assert_eq!(b"HTTP/1.1 503 Service Unavailable\r\n\r\n", serialized.as_slice());
+
+    // Parse the status code of a response header
+    let header = Header::read(&mut helpers::source(serialized)).expect("Failed to read header");
+    assert_eq!(status, header.status_typed().expect("Invalid status code"));
+    assert_eq!(503, u16::from(status));
+}
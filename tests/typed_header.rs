@@ -0,0 +1,154 @@
+mod helpers;
+
+use http_tiny::{
+    typed::{Connection, ContentLength, ContentType, Date, HostField, Location, TransferEncoding, UserAgent},
+    Error, Header, HeaderFields, HttpDate, TypedHeader,
+};
+use std::{
+    fmt::Debug,
+    marker::PhantomData,
+    time::{Duration, UNIX_EPOCH},
+};
+
+struct Test<T> {
+    values: &'static [&'static [u8]],
+    field: T,
+    encoded: &'static [u8],
+}
+impl<T> Test<T>
+where
+    T: TypedHeader + PartialEq + Debug,
+{
+    fn test(self) {
+        let field = T::decode(self.values).expect("Failed to decode field");
+        assert_eq!(self.field, field, "Unexpected field for {} {:?}", T::NAME, self.values);
+
+        // Ensure that the encoded field decodes to the same value
+        let encoded = field.encode();
+        assert_eq!(String::from_utf8_lossy(self.encoded), String::from_utf8_lossy(&encoded));
+        assert_eq!(field, T::decode(&[&encoded]).expect("Failed to decode encoded field"));
+    }
+}
+#[test]
+fn test_decode() {
+    Test { values: &[b"42", b"42, 42"], field: ContentLength(42), encoded: b"42" }.test();
+    Test {
+        values: &[b"text/html; charset=utf-8"],
+        field: ContentType::new(b"text/html; charset=utf-8").expect("Invalid content type"),
+        encoded: b"text/html; charset=utf-8",
+    }
+    .test();
+    Test {
+        values: &[b"example.com:8080"],
+        field: HostField::new(b"example.com:8080").expect("Invalid host"),
+        encoded: b"example.com:8080",
+    }
+    .test();
+    Test {
+        values: &[b"Upgrade, , keep-alive", b"HTTP2-Settings"],
+        field: Connection::new([b"Upgrade" as &[u8], b"keep-alive", b"HTTP2-Settings"]).expect("Invalid connection"),
+        encoded: b"Upgrade, keep-alive, HTTP2-Settings",
+    }
+    .test();
+    Test {
+        values: &[b"gzip;level=9", b"chunked"],
+        field: TransferEncoding::new([b"gzip;level=9" as &[u8], b"chunked"]).expect("Invalid transfer encoding"),
+        encoded: b"gzip;level=9, chunked",
+    }
+    .test();
+    Test {
+        values: &[b"Thu, 01 Jan 1970 00:01:00 GMT"],
+        field: Date(HttpDate::from(UNIX_EPOCH + Duration::from_secs(60))),
+        encoded: b"Thu, 01 Jan 1970 00:01:00 GMT",
+    }
+    .test();
+    Test {
+        values: &[b"/new/path?x=1"],
+        field: Location::new(b"/new/path?x=1").expect("Invalid location"),
+        encoded: b"/new/path?x=1",
+    }
+    .test();
+    Test {
+        values: &[b"curl/8.5.0 (x86_64)"],
+        field: UserAgent::new(b"curl/8.5.0 (x86_64)").expect("Invalid user agent"),
+        encoded: b"curl/8.5.0 (x86_64)",
+    }
+    .test();
+}
+
+#[derive(Debug)]
+struct TestErr<T> {
+    values: &'static [&'static [u8]],
+    error: Error,
+    field: PhantomData<T>,
+}
+impl<T> TestErr<T>
+where
+    T: TypedHeader + Debug,
+{
+    fn test(self) {
+        let error = match T::decode(self.values) {
+            Err(error) => error,
+            Ok(field) => panic!("Unexpected `Ok` for {} {:?} ({field:?})", T::NAME, self.values),
+        };
+        assert_eq!(self.error.to_string(), error.to_string(), "Unexpected error for {} {:?}", T::NAME, self.values);
+    }
+}
+#[test]
+fn test_decode_err() {
+    TestErr::<ContentLength> { values: &[b"42", b"43"], error: Error::InvalidContentLength, field: PhantomData }.test();
+    TestErr::<ContentType> {
+        values: &[b"text/html", b"text/plain"],
+        error: Error::InvalidFieldValue { offset: 0 },
+        field: PhantomData,
+    }
+    .test();
+    TestErr::<HostField> { values: &[b""], error: Error::InvalidFieldValue { offset: 0 }, field: PhantomData }.test();
+    TestErr::<Connection> {
+        values: &[b"close, keep alive"],
+        error: Error::InvalidFieldValue { offset: 4 },
+        field: PhantomData,
+    }
+    .test();
+    TestErr::<TransferEncoding> {
+        values: &[b"chunked, gzip"],
+        error: Error::InvalidTransferEncoding,
+        field: PhantomData,
+    }
+    .test();
+    TestErr::<TransferEncoding> { values: &[b" , "], error: Error::InvalidTransferEncoding, field: PhantomData }.test();
+    TestErr::<Location> { values: &[b"/new path"], error: Error::InvalidFieldValue { offset: 4 }, field: PhantomData }
+        .test();
+}
+
+#[test]
+fn test_fields() {
+    let raw = b"HTTP/1.1 301 Moved Permanently\r\nLocation: /moved\r\nConnection: close\r\nConnection: Upgrade\r\n\r\n";
+    let mut header = Header::read(&mut helpers::source(raw)).expect("Failed to read header");
+    let location = header.fields().typed_get::<Location>().expect("Invalid location").expect("Missing location");
+    assert_eq!(b"/moved", location.as_bytes());
+    let connection =
+        header.fields().typed_get::<Connection>().expect("Invalid connection").expect("Missing connection");
+    assert!(connection.contains(b"CLOSE") && connection.contains(b"upgrade") && !connection.contains(b"keep-alive"));
+    assert_eq!(None, header.fields().typed_get::<ContentLength>().expect("Invalid content length"));
+
+    // Replace all occurrences of a field
+    header.fields_mut().typed_set(Connection::keep_alive());
+    header.fields_mut().typed_set(ContentLength(7));
+    let mut serialized = Vec::new();
+    header.write_all(&mut serialized).expect("Failed to write header");
+    let expected =
+        b"HTTP/1.1 301 Moved Permanently\r\nLocation: /moved\r\nconnection: keep-alive\r\ncontent-length: 7\r\n\r\n";
+    assert_eq!(expected, serialized.as_slice());
+
+    // Build fields from typed values
+    let mut fields = HeaderFields::new();
+    fields.typed_set(HostField::new(b"[::1]:8080").expect("Invalid host"));
+    fields.typed_set(TransferEncoding::chunked());
+    fields.typed_set(UserAgent::new(b"http_tiny").expect("Invalid user agent"));
+    fields.typed_set(Connection::new([b"close" as &[u8]]).expect("Invalid connection"));
+    fields.typed_set(ContentType::new(b"text/plain").expect("Invalid content type"));
+    assert_eq!(Some(b"[::1]:8080" as &[u8]), fields.get("Host"));
+    assert_eq!(Some(true), fields.is_chunked().expect("Invalid transfer encoding"));
+    assert_eq!(5, fields.len());
+}
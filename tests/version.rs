@@ -0,0 +1,189 @@
+mod helpers;
+
+use http_tiny::{
+    bytetraits::IntoBytes, Error, Header, HeaderFields, HeaderParser, HeaderRef, HeaderStartLine, ReadOptions, Version,
+};
+use std::{
+    borrow::Cow,
+    io::{BufRead, ErrorKind},
+    iter::FromIterator,
+};
+
+struct Test {
+    raw: &'static [u8],
+    version: Version,
+}
+impl Test {
+    fn test(self) {
+        let version = Version::parse(self.raw).expect("Failed to parse version");
+        assert_eq!(self.version, version, "Unexpected version for {:?}", String::from_utf8_lossy(self.raw));
+    }
+}
+#[test]
+fn test_parse() {
+    Test { raw: b"HTTP/1.1", version: Version::Http11 }.test();
+    Test { raw: b"HTTP/1.0", version: Version::Http10 }.test();
+    Test { raw: b"HTTP/0.9", version: Version::Http09 }.test();
+}
+
+#[derive(Debug)]
+struct TestErr {
+    raw: &'static [u8],
+    error: Error,
+}
+impl TestErr {
+    fn test(self) {
+        let error = match Version::parse(self.raw) {
+            Err(error) => error,
+            Ok(version) => panic!("Unexpected `Ok` for {:?} ({version:?})", String::from_utf8_lossy(self.raw)),
+        };
+        assert_eq!(
+            self.error.to_string(),
+            error.to_string(),
+            "Unexpected error for {:?}",
+            String::from_utf8_lossy(self.raw)
+        );
+    }
+}
+#[test]
+fn test_parse_err() {
+    TestErr { raw: b"HTTP/2.0", error: Error::InvalidStartLine { offset: 0 } }.test();
+    TestErr { raw: b"HTTP/1", error: Error::InvalidStartLine { offset: 6 } }.test();
+    TestErr { raw: b"http/1.1", error: Error::InvalidStartLine { offset: 0 } }.test();
+}
+
+struct TestKeepAlive {
+    raw: &'static [u8],
+    is_keep_alive: bool,
+}
+impl TestKeepAlive {
+    fn test(self) {
+        let header = Header::read(&mut helpers::source(self.raw)).expect("Failed to read header");
+        assert_eq!(self.is_keep_alive, header.is_keep_alive(), "{}", String::from_utf8_lossy(self.raw));
+    }
+}
+#[test]
+fn test_keep_alive() {
+    TestKeepAlive { raw: b"GET / HTTP/1.1\r\n\r\n", is_keep_alive: true }.test();
+    TestKeepAlive { raw: b"GET / HTTP/1.1\r\nConnection: Upgrade, Close\r\n\r\n", is_keep_alive: false }.test();
+    TestKeepAlive { raw: b"GET / HTTP/1.0\r\n\r\n", is_keep_alive: false }.test();
+    TestKeepAlive { raw: b"HTTP/1.0 200 OK\r\nConnection: keep-alive\r\n\r\n", is_keep_alive: true }.test();
+    TestKeepAlive { raw: b"GET / HTTP/1.0\r\nConnection: keep-alive, close\r\n\r\n", is_keep_alive: false }.test();
+    TestKeepAlive { raw: b"GET / HTTP/3.0\r\n\r\n", is_keep_alive: false }.test();
+}
+
+#[test]
+fn test_properties() {
+    assert!(Version::Http11.requires_host() && !Version::Http10.requires_host());
+    assert!(Version::Http10.has_fields() && !Version::Http09.has_fields());
+
+    // Build and serialize a HTTP/1.0 request
+    let start_line = HeaderStartLine::new_request_with_version("GET", "/", Version::Http10);
+    let header = Header::new(start_line, HeaderFields::from_iter([("User-Agent", "check/1.0")]));
+    assert_eq!(Version::Http10, header.start_line().version_typed().expect("Invalid version"));
+    let mut serialized = Vec::new();
+    header.write_all(&mut serialized).expect("Failed to write header");
+    assert_eq!(b"GET / HTTP/1.0\r\nUser-Agent: check/1.0\r\n\r\n", serialized.as_slice());
+
+    // Build a HTTP/1.0 response
+    let start_line = HeaderStartLine::new_response_with_version(Version::Http10, 200, "OK");
+    assert_eq!(b"HTTP/1.0", start_line.version());
+
+    // Build validated start lines
+    let start_line =
+        HeaderStartLine::try_new_request_with_version("GET", "/", Version::Http10).expect("Invalid request");
+    assert_eq!(b"HTTP/1.0", start_line.version());
+    let start_line =
+        HeaderStartLine::try_new_response_with_version(Version::Http10, 404, "").expect("Invalid response");
+    assert_eq!(b"HTTP/1.0", start_line.version());
+    assert!(matches!(
+        HeaderStartLine::try_new_request_with_version("GET", "/a b", Version::Http10),
+        Err(Error::InvalidStartLine { offset: 6 })
+    ));
+    assert!(matches!(
+        HeaderStartLine::try_new_response_with_version(Version::Http09, 200, "OK"),
+        Err(Error::InvalidStartLine { offset: 0 })
+    ));
+}
+
+#[test]
+fn test_http09() {
+    const RAW: &[u8] = b"GET /index.html\r\nNEXT";
+    let options = ReadOptions { allow_http09: true, ..Default::default() };
+
+    // Read the simple request
+    let mut source = helpers::source(RAW);
+    let header = Header::read_with(&mut source, &options).expect("Failed to read header");
+    assert_eq!(Some(b"/index.html" as &[u8]), header.start_line().target());
+    assert_eq!(Version::Http09, header.start_line().version_typed().expect("Invalid version"));
+    assert!(header.fields().is_empty());
+
+    // Parse the simple request
+    let (parsed, consumed) = HeaderParser::with_options(options)
+        .parse(RAW)
+        .expect("Failed to parse header")
+        .complete()
+        .expect("Partial header");
+    assert_eq!((&header, 17), (&parsed, consumed));
+    let (borrowed, consumed) =
+        HeaderRef::parse_with(RAW, &options).expect("Failed to parse header").complete().expect("Partial header");
+    assert_eq!((&header, 17), (&borrowed.to_owned(), consumed));
+
+    // Write the simple request back without version and header fields
+    let mut serialized = Vec::new();
+    header.write_all(&mut serialized).expect("Failed to write header");
+    assert_eq!(b"GET /index.html\r\n" as &[u8], serialized.as_slice());
+    let start_line = HeaderStartLine::new_request_with_version("GET", "/", Version::Http09);
+    let mut serialized = Vec::new();
+    Header::new(start_line.clone(), HeaderFields::new()).write_all(&mut serialized).expect("Failed to write header");
+    assert_eq!(b"GET /\r\n" as &[u8], serialized.as_slice());
+
+    // Reject header fields for simple requests and HTTP/0.9 status lines
+    let header = Header::new(start_line, HeaderFields::from_iter([("Host", "localhost")]));
+    let error = header.write_all(&mut Vec::new()).expect_err("Unexpected header fields for HTTP/0.9");
+    assert_eq!(ErrorKind::InvalidInput, error.kind());
+    let start_line = HeaderStartLine::Response {
+        version: Version::Http09.into_bytes(),
+        status: Cow::Borrowed(b"200"),
+        reason: Cow::Borrowed(b"OK"),
+    };
+    let error = start_line.write_all(&mut Vec::new()).expect_err("Unexpected HTTP/0.9 status line");
+    assert_eq!(ErrorKind::InvalidInput, error.kind());
+
+    // Reject simple requests with other methods
+    assert!(matches!(
+        HeaderStartLine::try_new_request_with_version("POST", "/", Version::Http09),
+        Err(Error::InvalidStartLine { offset: 0 })
+    ));
+
+    // Reject simple requests by default or with other methods
+    assert!(matches!(Header::parse(RAW), Err(Error::InvalidStartLine { offset: 0 })));
+    assert!(matches!(Header::parse_with(b"POST /\r\n", &options), Err(Error::InvalidStartLine { offset: 0 })));
+}
+
+#[test]
+fn test_http09_full_request_line() {
+    const RAWS: [&[u8]; 2] = [b"GET / HTTP/0.9\r\n\r\n", b"GET / HTTP/0.9\r\nHost: x\r\n\r\n"];
+    let options = ReadOptions { allow_http09: true, ..Default::default() };
+    for raw in RAWS {
+        for options in [ReadOptions::default(), options] {
+            // Read the full request line together with its header fields
+            let mut source = helpers::source(raw);
+            let header = Header::read_with(&mut source, &options).expect("Failed to read header");
+            assert!(source.fill_buf().expect("Failed to fill buffer").is_empty());
+            let (parsed, consumed) =
+                Header::parse_with(raw, &options).expect("Failed to parse header").complete().unwrap();
+            assert_eq!((&header, raw.len()), (&parsed, consumed));
+            let (borrowed, consumed) =
+                HeaderRef::parse_with(raw, &options).expect("Failed to parse").complete().unwrap();
+            assert_eq!((&header, raw.len()), (&borrowed.to_owned(), consumed));
+
+            // Write the headers back unchanged
+            for header in [header, parsed, borrowed.to_owned()] {
+                let mut serialized = Vec::new();
+                header.write_all(&mut serialized).expect("Failed to write header");
+                assert_eq!(String::from_utf8_lossy(raw), String::from_utf8_lossy(&serialized));
+            }
+        }
+    }
+}